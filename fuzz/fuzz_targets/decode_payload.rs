@@ -0,0 +1,14 @@
+#![no_main]
+
+use ais::messages;
+use libfuzzer_sys::fuzz_target;
+
+// Treats the first byte as the fill bit count, and the rest as an armored payload
+fuzz_target!(|data: &[u8]| {
+    if let Some((&fill_bits, payload)) = data.split_first() {
+        if let Ok(unarmored) = messages::unarmor(payload, fill_bits as usize) {
+            let _ = messages::parse(&unarmored);
+        }
+    }
+    let _ = messages::parse(data);
+});
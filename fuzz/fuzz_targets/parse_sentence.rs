@@ -0,0 +1,12 @@
+#![no_main]
+
+use ais::AisParser;
+use libfuzzer_sys::fuzz_target;
+
+// Feeds each line to a single parser, so fragment reassembly gets exercised too
+fuzz_target!(|data: &[u8]| {
+    let mut parser = AisParser::new();
+    for line in data.split(|&byte| byte == b'\n') {
+        let _ = parser.parse(line, true);
+    }
+});
@@ -1,35 +1,116 @@
 use ais::lib;
 
+use ais::reader::Lines;
 use ais::sentence::{AisFragments, AisParser};
-use lib::std::io::BufRead;
 
-use lib::std::io;
+use lib::std::io::{self, BufRead, Write};
+use std::process;
 
-fn parse_nmea_line(parser: &mut AisParser, line: &[u8]) -> Result<(), ais::errors::Error> {
+fn parse_nmea_line(
+    parser: &mut AisParser,
+    line: &[u8],
+    output: &mut impl Write,
+) -> Result<(), ais::errors::Error> {
     let sentence = parser.parse(line, true)?;
     if let AisFragments::Complete(sentence) = sentence {
-        println!(
+        writeln!(
+            output,
             "{:?}\t{:?}",
-            lib::std::str::from_utf8(line).unwrap(),
+            lib::std::string::String::from_utf8_lossy(line),
             sentence.message
-        );
+        )?;
     }
     Ok(())
 }
 
-fn main() {
+/// Decodes each line of `input` to `output`. Bad sentences are reported to
+/// `errors` and skipped, and blank lines are ignored, but a failing input or
+/// output stream ends the run. Returns how many lines failed to parse, after
+/// reporting that count to `errors`.
+fn run(input: impl BufRead, output: &mut impl Write, errors: &mut impl Write) -> io::Result<usize> {
     let mut parser = AisParser::new();
+    let mut failures = 0;
+    for line in Lines::new(input) {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        match parse_nmea_line(&mut parser, &line, output) {
+            Ok(()) => {}
+            Err(ais::errors::Error::Io { kind, msg }) => return Err(io::Error::new(kind, msg)),
+            Err(err) => {
+                failures += 1;
+                writeln!(
+                    errors,
+                    "{:?}\t{:?}",
+                    lib::std::string::String::from_utf8_lossy(&line),
+                    err
+                )?
+            }
+        }
+    }
+    writeln!(errors, "{} lines failed to parse", failures)?;
+    Ok(failures)
+}
+
+fn main() {
     let stdin = io::stdin();
-    {
-        let handle = stdin.lock();
-
-        handle
-            .split(b'\n')
-            .map(|line| line.unwrap())
-            .for_each(|line| {
-                parse_nmea_line(&mut parser, &line).unwrap_or_else(|err| {
-                    eprintln!("{:?}\t{:?}", lib::std::str::from_utf8(&line).unwrap(), err);
-                });
-            });
+    let stdout = io::stdout();
+    let stderr = io::stderr();
+    if let Err(err) = run(stdin.lock(), &mut stdout.lock(), &mut stderr.lock()) {
+        eprintln!("Error reading input: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib::std::io::{BufReader, Cursor, Read};
+
+    /// Yields `data`, then fails
+    struct FailingReader<'a>(&'a [u8]);
+
+    impl Read for FailingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "gone"));
+            }
+            self.0.read(buf)
+        }
+    }
+
+    const INPUT: &[u8] = b"garbage
+!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24
+!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*25
+
+!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21
+";
+
+    #[test]
+    fn skips_bad_lines() {
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let failures = run(Cursor::new(INPUT), &mut output, &mut errors).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let errors = String::from_utf8(errors).unwrap();
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.contains("265547250"));
+        assert!(output.contains("3669145"));
+        // The garbage and the bad checksum, but not the empty line
+        assert_eq!(failures, 2);
+        let errors: Vec<_> = errors.lines().collect();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[1].contains("Checksum"));
+        assert_eq!(errors[2], "2 lines failed to parse");
+    }
+
+    #[test]
+    fn stops_on_read_error() {
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let input = BufReader::new(FailingReader(INPUT));
+        let err = run(input, &mut output, &mut errors).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        // Everything before the failure is still decoded
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), 2);
     }
 }
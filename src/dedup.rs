@@ -0,0 +1,102 @@
+//! Dropping retransmitted copies of addressed messages from a decoded feed
+use crate::lib::std::collections::HashMap;
+use crate::messages::AisMessage;
+
+/// Spots retransmissions of addressed messages which have already been seen, so
+/// they aren't processed twice.
+///
+/// Addressed messages are identified by their source and destination MMSIs and
+/// their sequence number. Sequence numbers only go from 0 to 3, so an original
+/// message with a sequence number which has been seen before is taken to be a
+/// new message. Only messages with the retransmit flag set are ever reported
+/// as duplicates, and then only if the same message has already been seen. A
+/// retransmission whose original was missed is passed through as new.
+///
+/// # Example:
+/// ```
+/// use ais::dedup::AddressedDedup;
+/// use ais::messages::{parse, unarmor};
+///
+/// let mut dedup = AddressedDedup::new();
+/// let message = parse(&unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0)?)?;
+/// // Messages which aren't addressed are never duplicates
+/// assert!(!dedup.is_duplicate(&message));
+/// assert!(!dedup.is_duplicate(&message));
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct AddressedDedup {
+    /// The last sequence number seen from each source to each destination
+    last_sequence: HashMap<(u32, u32), u8>,
+}
+
+impl AddressedDedup {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records `message`, returning whether it is a retransmission of a message
+    /// which has already been seen
+    pub fn is_duplicate(&mut self, message: &AisMessage) -> bool {
+        let (key, sequence_number, retransmit) = match message {
+            AisMessage::AddressedBinaryMessage(message) => (
+                (message.mmsi, message.dest_mmsi),
+                message.sequence_number,
+                message.retransmit,
+            ),
+            _ => return false,
+        };
+        let previous = self.last_sequence.insert(key, sequence_number);
+        retransmit && previous == Some(sequence_number)
+    }
+
+    /// Forgets all the messages seen so far
+    pub fn clear(&mut self) {
+        self.last_sequence.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::addressed_binary_message::AddressedBinaryMessage;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    fn addressed(source: u64, sequence_number: u64, retransmit: bool) -> AisMessage {
+        let bitstream = pack_bits(&[
+            (6, 6),
+            (0, 2),
+            (source, 30),
+            (sequence_number, 2),
+            (244660000, 30),
+            (retransmit as u64, 1),
+            (0, 1),
+            (235, 10),
+            (10, 6),
+            (0xbeef, 16),
+        ]);
+        let message = AddressedBinaryMessage::parse(bitstream.as_ref()).unwrap();
+        AisMessage::AddressedBinaryMessage(message)
+    }
+
+    #[test]
+    fn drops_retransmits() {
+        let mut dedup = AddressedDedup::new();
+        let original = addressed(211234560, 2, false);
+        let retransmit = addressed(211234560, 2, true);
+        assert_eq!(retransmit.retransmit(), Some(true));
+        assert!(!dedup.is_duplicate(&original));
+        assert!(dedup.is_duplicate(&retransmit));
+        assert!(dedup.is_duplicate(&retransmit));
+        // The same sequence number from another sender is a different message
+        assert!(!dedup.is_duplicate(&addressed(211234561, 2, true)));
+        // Sequence numbers wrap around, so an original is always new
+        assert!(!dedup.is_duplicate(&original));
+        assert!(!dedup.is_duplicate(&addressed(211234560, 3, true)));
+
+        dedup.clear();
+        assert!(!dedup.is_duplicate(&retransmit));
+    }
+}
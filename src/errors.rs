@@ -5,6 +5,7 @@ pub use err::*;
 #[cfg(any(feature = "std", feature = "alloc"))]
 mod err {
     use crate::lib;
+    use crate::tag_block::TagBlockError;
     use lib::std::format;
     use lib::std::string::{String, ToString};
 
@@ -14,14 +15,39 @@ mod err {
     #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
     pub enum Error {
         //#[error("invalid NMEA sentence: '{msg}'")]
-        Nmea { msg: String },
+        Nmea {
+            msg: String,
+        },
         //#[error("checksum mismatch; expected: {expected:#X}, received: {found:#X}")]
-        Checksum { expected: u8, found: u8 },
+        Checksum {
+            expected: u8,
+            found: u8,
+        },
+        /// The tag block in front of a sentence is invalid
+        TagBlock(TagBlockError),
+        /// The message is of a type this crate can't decode yet
+        UnsupportedMessageType(u8),
+        /// Reading the input failed
+        #[cfg(feature = "std")]
+        Io {
+            kind: std::io::ErrorKind,
+            msg: String,
+        },
     }
 
     #[cfg(feature = "std")]
     impl std::error::Error for Error {}
 
+    #[cfg(feature = "std")]
+    impl From<std::io::Error> for Error {
+        fn from(err: std::io::Error) -> Self {
+            Self::Io {
+                kind: err.kind(),
+                msg: err.to_string(),
+            }
+        }
+    }
+
     impl core::fmt::Display for Error {
         fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
             let msg = match self {
@@ -29,11 +55,23 @@ mod err {
                 Self::Checksum { expected, found } => {
                     format!("Checksum error; expected 0x{expected:x}, found 0x{found:x}")
                 }
+                Self::TagBlock(err) => err.to_string(),
+                Self::UnsupportedMessageType(message_type) => {
+                    format!("Unimplemented type: {}", message_type)
+                }
+                #[cfg(feature = "std")]
+                Self::Io { msg, .. } => format!("I/O error: {}", msg),
             };
             f.write_str(&msg)
         }
     }
 
+    impl From<TagBlockError> for Error {
+        fn from(err: TagBlockError) -> Self {
+            Self::TagBlock(err)
+        }
+    }
+
     impl From<&str> for Error {
         fn from(err: &str) -> Self {
             Self::Nmea { msg: err.into() }
@@ -74,6 +112,7 @@ mod err {
 #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
 mod err {
     use crate::lib;
+    use crate::tag_block::TagBlockError;
 
     /// Custom `Result` to prepopulate `Error` type
     pub type Result<T> = lib::std::result::Result<T, Error>;
@@ -81,9 +120,24 @@ mod err {
     #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
     pub enum Error {
         //#[error("invalid NMEA sentence: '{msg}'")]
-        Nmea { msg: &'static str },
+        Nmea {
+            msg: &'static str,
+        },
         //#[error("checksum mismatch; expected: {expected:#X}, received: {found:#X}")]
-        Checksum { expected: u8, found: u8 },
+        Checksum {
+            expected: u8,
+            found: u8,
+        },
+        /// The tag block in front of a sentence is invalid
+        TagBlock(TagBlockError),
+        /// The message is of a type this crate can't decode yet
+        UnsupportedMessageType(u8),
+    }
+
+    impl From<TagBlockError> for Error {
+        fn from(err: TagBlockError) -> Self {
+            Self::TagBlock(err)
+        }
     }
 
     impl From<&'static str> for Error {
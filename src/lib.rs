@@ -69,19 +69,35 @@ pub mod lib {
     #[cfg(feature = "std")]
     pub mod std {
         #[doc(hidden)]
-        pub use std::{borrow, cmp, error, fmt, format, io, mem, result, str, string, vec};
+        pub use std::{
+            borrow, boxed, cmp, collections, error, fmt, format, fs, io, mem, net, path, result,
+            str, string, time, vec,
+        };
     }
 }
 
+#[cfg(feature = "std")]
+pub mod dedup;
 pub mod errors;
 pub mod messages;
+#[cfg(feature = "std")]
+pub mod reader;
 pub mod sentence;
+#[cfg(feature = "std")]
+pub mod stats;
+pub mod tag_block;
+#[cfg(feature = "std")]
+pub mod tracker;
 
 pub use errors::Result;
-pub use sentence::{AisFragments, AisParser};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use sentence::SentenceBuilder;
+pub use sentence::{AisFragments, AisParser, Channel};
 
 #[cfg(test)]
 mod test_helpers {
+    use crate::sentence::AisRawData;
+
     #[inline]
     /// Compares two `f32`s, assuming they are both numeric, and panics if they differ
     pub fn f32_equal_naive(a: f32, b: f32) {
@@ -89,13 +105,48 @@ mod test_helpers {
             panic!("float {} != {}", a, b);
         }
     }
+
+    /// Packs `(value, bit count)` fields, most significant bit first, into an
+    /// unarmored bitstream. Values are truncated to their bit count. Fields can
+    /// be wider than 64 bits, such as blank 120-bit names, in which case the
+    /// bits above the value are zero.
+    pub fn pack_bits(fields: &[(u64, usize)]) -> AisRawData {
+        let mut output = AisRawData::default();
+        let mut offset = 0;
+        for &(value, len) in fields {
+            for bit in (0..len).rev() {
+                if offset % 8 == 0 {
+                    #[cfg(any(feature = "std", feature = "alloc"))]
+                    output.push(0);
+                    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+                    output.push(0).unwrap();
+                }
+                if value.checked_shr(bit as u32).unwrap_or(0) & 1 == 1 {
+                    output[offset / 8] |= 0x80 >> (offset % 8);
+                }
+                offset += 1;
+            }
+        }
+        output
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::pack_bits;
 
-    const TEST_MESSAGES: [&[u8]; 8] = [
+    #[test]
+    fn pack_field_widths() {
+        assert!(pack_bits(&[(0xff, 0)]).is_empty());
+        assert_eq!(pack_bits(&[(u32::MAX as u64, 32)])[..], [0xff; 4]);
+        assert_eq!(pack_bits(&[(u64::MAX, 64)])[..], [0xff; 8]);
+        // Wider than the value, so zero-filled before it
+        assert_eq!(pack_bits(&[(1, 70)])[..], [0, 0, 0, 0, 0, 0, 0, 0, 0x04]);
+        assert_eq!(pack_bits(&[(1, 1), (0, 0), (1, 1)])[..], [0xc0]);
+    }
+
+    pub(crate) const TEST_MESSAGES: [&[u8]; 8] = [
         b"!AIVDM,1,1,,B,E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100,0*01",
         b"!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21",
         b"!AIVDM,1,1,,B,ENkb9U79PW@80Q67h10dh1T6@Hq;`0W8:peOH00003vP000,0*1C",
@@ -113,4 +164,37 @@ mod tests {
             parser.parse(line, true).unwrap();
         }
     }
+
+    #[test]
+    fn verify_test_message_checksums() {
+        for line in TEST_MESSAGES.iter() {
+            let line = lib::std::str::from_utf8(line).unwrap();
+            assert!(sentence::verify_sentence_checksum(line), "{}", line);
+            let (body, checksum) = line[1..].rsplit_once('*').unwrap();
+            assert_eq!(
+                sentence::nmea_checksum(body.as_bytes()),
+                u8::from_str_radix(checksum, 16).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn end_to_end_skipping_corrupt_lines() {
+        let corrupt: [&[u8]; 3] = [
+            b"!AIVDM,1,1,,B,E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100,0*02",
+            b"!AIVDM,2,3,7,B,0000000,2*21",
+            b"garbage",
+        ];
+        let mut parser = sentence::AisParser::new();
+        let mut decoded = 0;
+        for (idx, line) in TEST_MESSAGES.iter().enumerate() {
+            // Errors, including one in the middle of a fragmented message, leave the parser usable
+            assert!(parser.parse(corrupt[idx % corrupt.len()], true).is_err());
+            if let AisFragments::Complete(sentence) = parser.parse(line, true).unwrap() {
+                assert!(sentence.message.is_some());
+                decoded += 1;
+            }
+        }
+        assert_eq!(decoded, TEST_MESSAGES.len() - 1);
+    }
 }
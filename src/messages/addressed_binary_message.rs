@@ -0,0 +1,169 @@
+//! Addressed Binary Message (type 6)
+use super::binary_broadcast_message::MessageData;
+use super::inland_eta::InlandEta;
+use super::inland_rta::InlandRta;
+use super::parsers::u8_to_bool;
+use super::AisMessageType;
+use crate::errors::Result;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AddressedBinaryMessage {
+    pub message_type: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub sequence_number: u8,
+    pub dest_mmsi: u32,
+    pub retransmit: bool,
+    /// Designated area code
+    pub dac: u16,
+    /// Functional ID
+    pub fid: u8,
+    pub data: MessageData,
+}
+
+impl<'a> AisMessageType<'a> for AddressedBinaryMessage {
+    fn name(&self) -> &'static str {
+        "Addressed Binary Message"
+    }
+
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        let (_, report) = parse_base(data)?;
+        Ok(report)
+    }
+}
+
+/// Application specific messages which can be carried in the binary payload,
+/// identified by their DAC and FI
+#[derive(Debug, PartialEq)]
+pub enum AddressedApplicationData {
+    /// DAC 200, FI 21
+    InlandEta(InlandEta),
+    /// DAC 200, FI 22
+    InlandRta(InlandRta),
+}
+
+impl AddressedBinaryMessage {
+    /// The binary payload as plain bytes, whatever its DAC and FI, for archiving
+    /// payloads this library can't decode.
+    ///
+    /// The header ends on a byte boundary, so these are exactly the payload
+    /// bits, except that the last byte may be padded out with zeros.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn payload_bytes(&self) -> lib::std::vec::Vec<u8> {
+        self.data.to_vec()
+    }
+
+    /// Decodes the binary payload according to its DAC and FI.
+    ///
+    /// Returns `Ok(None)` if the combination isn't supported by this library.
+    pub fn application_data(&self) -> Result<Option<AddressedApplicationData>> {
+        match (self.dac, self.fid) {
+            (200, 21) => Ok(Some(AddressedApplicationData::InlandEta(InlandEta::parse(
+                &self.data,
+            )?))),
+            (200, 22) => Ok(Some(AddressedApplicationData::InlandRta(InlandRta::parse(
+                &self.data,
+            )?))),
+            _ => Ok(None),
+        }
+    }
+}
+
+fn parse_base<'a>(data: &'a [u8]) -> IResult<&'a [u8], AddressedBinaryMessage> {
+    bits(move |data: (&'a [u8], usize)| -> IResult<_, _> {
+        let (data, message_type) = take_bits(6u8)(data)?;
+        let (data, repeat_indicator) = take_bits(2u8)(data)?;
+        let (data, mmsi) = take_bits(30u32)(data)?;
+        let (data, sequence_number) = take_bits(2u8)(data)?;
+        let (data, dest_mmsi) = take_bits(30u32)(data)?;
+        let (data, retransmit) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(1u8)(data)?;
+        let (data, dac) = take_bits(10u16)(data)?;
+        let (data, fid) = take_bits(6u8)(data)?;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        let data_owned = data.0.into();
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        let data_owned = data.0.try_into().map_err(|_| {
+            nom::Err::Failure(nom::error::Error::new(
+                data,
+                nom::error::ErrorKind::TooLarge,
+            ))
+        })?;
+        Ok((
+            (<&[u8]>::default(), 0),
+            AddressedBinaryMessage {
+                message_type,
+                repeat_indicator,
+                mmsi,
+                sequence_number,
+                dest_mmsi,
+                retransmit,
+                dac,
+                fid,
+                data: data_owned,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_addressed_binary_message() {
+        let bitstream = pack_bits(&[
+            (6, 6),
+            (1, 2),
+            (211234560, 30),
+            (2, 2),
+            (244660000, 30),
+            (1, 1),
+            (0, 1),
+            (235, 10),
+            (10, 6),
+            (0xbeef, 16),
+        ]);
+        let message = AddressedBinaryMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(message.message_type, 6);
+        assert_eq!(message.repeat_indicator, 1);
+        assert_eq!(message.mmsi, 211234560);
+        assert_eq!(message.sequence_number, 2);
+        assert_eq!(message.dest_mmsi, 244660000);
+        assert!(message.retransmit);
+        assert_eq!(message.dac, 235);
+        assert_eq!(message.fid, 10);
+        assert_eq!(&message.data[..], &[0xbe, 0xef]);
+        assert_eq!(message.application_data().unwrap(), None);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn test_payload_bytes() {
+        // An unknown DAC, with 40 bits of payload
+        let bitstream = pack_bits(&[
+            (6, 6),
+            (0, 2),
+            (211234560, 30),
+            (0, 2),
+            (244660000, 30),
+            (0, 1),
+            (0, 1),
+            (999, 10),
+            (1, 6),
+            (0x0102030405, 40),
+        ]);
+        let message = AddressedBinaryMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(message.application_data().unwrap(), None);
+        let payload = message.payload_bytes();
+        assert_eq!(payload.len(), 5);
+        assert_eq!(payload, [1, 2, 3, 4, 5]);
+    }
+}
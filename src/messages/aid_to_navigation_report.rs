@@ -84,6 +84,9 @@ impl NavaidType {
     }
 }
 
+/// The name extension is at most 14 characters, followed by any padding
+const MAX_NAME_EXTENSION_BITS: usize = 84;
+
 #[derive(Debug, PartialEq)]
 pub struct AidToNavigationReport {
     pub message_type: u8,
@@ -94,17 +97,27 @@ pub struct AidToNavigationReport {
     pub accuracy: Accuracy,
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
     pub dimension_to_bow: u16,
     pub dimension_to_stern: u16,
     pub dimension_to_port: u16,
     pub dimension_to_starboard: u16,
     pub epfd_type: Option<EpfdType>,
     pub utc_second: u8,
+    /// Whether a floating aid has drifted from its charted position. Only
+    /// meaningful when `utc_second` is below 60.
     pub off_position: bool,
     pub regional_reserved: u8,
     pub raim: bool,
+    /// Whether the aid only exists as this broadcast, with nothing physically
+    /// at the position
     pub virtual_aid: bool,
+    /// Whether the aid is operating as assigned by a type 16 or 23 command
     pub assigned_mode: bool,
+    /// Up to 14 more characters of the name, for names longer than 20
+    /// characters. Empty if the message wasn't extended.
+    pub name_extension: AsciiString,
 }
 
 impl<'a> AisMessageType<'a> for AidToNavigationReport {
@@ -126,8 +139,8 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], AidToNavigationReport> {
         let (data, aid_type) = map(take_bits(5u8), NavaidType::parse)(data)?;
         let (data, name) = parse_6bit_ascii(data, 120)?;
         let (data, accuracy) = map(take_bits(1u8), Accuracy::parse)(data)?;
-        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
-        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 28)?;
+        let (data, raw_latitude) = signed_i32(data, 27)?;
         let (data, dimension_to_bow) = take_bits(9u16)(data)?;
         let (data, dimension_to_stern) = take_bits(9u16)(data)?;
         let (data, dimension_to_port) = take_bits(6u16)(data)?;
@@ -140,6 +153,8 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], AidToNavigationReport> {
         let (data, virtual_aid) = map(take_bits(1u8), u8_to_bool)(data)?;
         let (data, assigned_mode) = map(take_bits(1u8), u8_to_bool)(data)?;
         let (data, _spare) = take_bits::<_, u8, _, _>(1u8)(data)?;
+        let extension_bits = (remaining_bits(data) / 6 * 6).min(MAX_NAME_EXTENSION_BITS);
+        let (data, name_extension) = parse_6bit_ascii(data, extension_bits)?;
         Ok((
             data,
             AidToNavigationReport {
@@ -149,8 +164,9 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], AidToNavigationReport> {
                 aid_type,
                 name,
                 accuracy,
-                longitude,
-                latitude,
+                longitude: parse_longitude(raw_longitude),
+                latitude: parse_latitude(raw_latitude),
+                coordinate: Coordinate::new(raw_latitude, raw_longitude),
                 dimension_to_bow,
                 dimension_to_stern,
                 dimension_to_port,
@@ -162,6 +178,7 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], AidToNavigationReport> {
                 raim,
                 virtual_aid,
                 assigned_mode,
+                name_extension,
             },
         ))
     })(data)
@@ -187,5 +204,91 @@ mod tests {
         f32_equal_naive(message.latitude.unwrap(), 38.124718);
         assert_eq!(message.epfd_type, Some(EpfdType::Surveyed));
         assert!(!message.raim);
+        assert!(!message.assigned_mode);
+        assert_eq!(message.name_extension, "");
+    }
+
+    #[test]
+    fn test_type21_flags() {
+        let fields = |utc_second, off_position, raim, virtual_aid, assigned| {
+            pack_bits(&[
+                (21, 6),
+                (0, 2),
+                (993692005, 30),
+                (20, 5),
+                (0, 120),
+                (0, 1),
+                (0, 28),
+                (0, 27),
+                (0, 30),
+                (7, 4),
+                (utc_second, 6),
+                (off_position, 1),
+                (0, 8),
+                (raim, 1),
+                (virtual_aid, 1),
+                (assigned, 1),
+                (0, 1),
+            ])
+        };
+        let message = AidToNavigationReport::parse(fields(60, 1, 1, 1, 1).as_ref()).unwrap();
+        assert!(message.off_position);
+        assert!(message.raim);
+        assert!(message.virtual_aid);
+        assert!(message.assigned_mode);
+        assert_eq!(message.utc_second, 60);
+
+        // Each flag on its own
+        for flag in 0..4 {
+            let bit = |n| (flag == n) as u64;
+            let bitstream = fields(30, bit(0), bit(1), bit(2), bit(3));
+            let message = AidToNavigationReport::parse(bitstream.as_ref()).unwrap();
+            assert_eq!(message.off_position, flag == 0);
+            assert_eq!(message.raim, flag == 1);
+            assert_eq!(message.virtual_aid, flag == 2);
+            assert_eq!(message.assigned_mode, flag == 3);
+        }
+    }
+
+    #[test]
+    fn test_type21_name_extension() {
+        // From !AIVDM,2,1,5,B,E1mg=5J1T4W0h97aRh6ba84<h2d;W:Te=eLvH50```q,0*46
+        // and !AIVDM,2,2,5,B,:D44QDlp0C1DU00,2*36
+        let bytestream = b"E1mg=5J1T4W0h97aRh6ba84<h2d;W:Te=eLvH50```q:D44QDlp0C1DU00";
+        let bitstream = crate::messages::unarmor(bytestream, 2).unwrap();
+        let message = AidToNavigationReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(message.mmsi, 123456789);
+        assert_eq!(message.aid_type, Some(NavaidType::CardinalMarkN));
+        assert_eq!(message.name, "CHINA ROSE MURPHY EX");
+        assert_eq!(message.name_extension, "PRESS ALERT");
+        assert_eq!(message.epfd_type, Some(EpfdType::Gps));
+        assert_eq!(message.dimension_to_bow, 5);
+        assert_eq!(message.utc_second, 50);
+    }
+
+    #[test]
+    fn test_type21_epfd_type() {
+        let fields = |epfd_type| {
+            pack_bits(&[
+                (21, 6),
+                (0, 2),
+                (993692005, 30),
+                (1, 5),
+                (0, 120),
+                (0, 1),
+                (0, 28),
+                (0, 27),
+                (0, 30),
+                (epfd_type, 4),
+                (60, 6),
+                (0, 14),
+            ])
+        };
+        let message = AidToNavigationReport::parse(fields(1).as_ref()).unwrap();
+        assert_eq!(message.epfd_type, Some(EpfdType::Gps));
+        let message = AidToNavigationReport::parse(fields(8).as_ref()).unwrap();
+        assert_eq!(message.epfd_type, Some(EpfdType::Galileo));
+        let message = AidToNavigationReport::parse(fields(0).as_ref()).unwrap();
+        assert_eq!(message.epfd_type, None);
     }
 }
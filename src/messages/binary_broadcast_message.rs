@@ -1,4 +1,8 @@
 //! Binary Broadcast Message (type 8)
+use super::extended_ship_static_data::ExtendedShipStaticData;
+use super::persons_on_board::PersonsOnBoard;
+use super::route_information::RouteInformation;
+use super::vts_generated_targets::VtsGeneratedTargets;
 use super::AisMessageType;
 use crate::errors::Result;
 use crate::lib;
@@ -36,6 +40,43 @@ impl<'a> AisMessageType<'a> for BinaryBroadcastMessage {
     }
 }
 
+/// Application specific messages which can be carried in the binary payload,
+/// identified by their DAC and FI
+#[derive(Debug, PartialEq)]
+pub enum BroadcastApplicationData {
+    /// DAC 1, FI 17
+    VtsGeneratedTargets(VtsGeneratedTargets),
+    /// DAC 1, FI 24
+    ExtendedShipStaticData(ExtendedShipStaticData),
+    /// DAC 1, FI 27
+    RouteInformation(RouteInformation),
+    /// DAC 1, FI 40
+    PersonsOnBoard(PersonsOnBoard),
+}
+
+impl BinaryBroadcastMessage {
+    /// Decodes the binary payload according to its DAC and FI.
+    ///
+    /// Returns `Ok(None)` if the combination isn't supported by this library.
+    pub fn application_data(&self) -> Result<Option<BroadcastApplicationData>> {
+        match (self.dac, self.fid) {
+            (1, 17) => Ok(Some(BroadcastApplicationData::VtsGeneratedTargets(
+                VtsGeneratedTargets::parse(&self.data)?,
+            ))),
+            (1, 24) => Ok(Some(BroadcastApplicationData::ExtendedShipStaticData(
+                ExtendedShipStaticData::parse(&self.data)?,
+            ))),
+            (1, 27) => Ok(Some(BroadcastApplicationData::RouteInformation(
+                RouteInformation::parse(&self.data)?,
+            ))),
+            (1, 40) => Ok(Some(BroadcastApplicationData::PersonsOnBoard(
+                PersonsOnBoard::parse(&self.data)?,
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum CarrierSense {
     /// Class B SOTDMA unit
@@ -0,0 +1,143 @@
+//! Reading fields out of unarmored AIS data, one at a time
+use super::parsers::{parse_6bit_ascii, remaining_bits, signed_i32, AsciiString};
+use crate::errors::Result;
+use nom::bits::complete::take as take_bits;
+use nom::IResult;
+
+/// Reads fields of arbitrary bit widths from unarmored data, most significant
+/// bit first, keeping track of the position. This is meant for decoding
+/// application specific payloads, such as the `data` of a binary message.
+///
+/// Reading past the end of the data returns an error, and leaves the position
+/// unchanged.
+///
+/// # Example:
+/// ```
+/// use ais::messages::{unarmor, BitReader};
+///
+/// let data = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0)?;
+/// let mut reader = BitReader::new(&data);
+/// assert_eq!(reader.read_u32(6)?, 1);
+/// reader.skip(2)?;
+/// assert_eq!(reader.read_u32(30)?, 265547250);
+/// assert_eq!(reader.position(), 38);
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    input: (&'a [u8], usize),
+    bit_count: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            input: (data, 0),
+            bit_count: data.len() * 8,
+        }
+    }
+
+    /// Number of bits read so far
+    pub fn position(&self) -> usize {
+        self.bit_count - self.remaining()
+    }
+
+    /// Number of bits left to read
+    pub fn remaining(&self) -> usize {
+        remaining_bits(self.input)
+    }
+
+    /// Runs one of the crate's bit parsers at the current position
+    pub(crate) fn run<T, F>(&mut self, parser: F) -> Result<T>
+    where
+        F: FnOnce((&'a [u8], usize)) -> IResult<(&'a [u8], usize), T>,
+    {
+        let (input, value) = parser(self.input)?;
+        self.input = input;
+        Ok(value)
+    }
+
+    /// Reads an unsigned number of up to 32 bits
+    pub fn read_u32(&mut self, bits: usize) -> Result<u32> {
+        if bits > 32 {
+            return Err("Can't read more than 32 bits into a u32".into());
+        }
+        self.run(take_bits(bits))
+    }
+
+    /// Reads a two's complement signed number of up to 32 bits
+    pub fn read_i32(&mut self, bits: usize) -> Result<i32> {
+        if bits == 0 || bits > 32 {
+            return Err("Can only read 1 to 32 bits into an i32".into());
+        }
+        self.run(|input| signed_i32(input, bits))
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool> {
+        Ok(self.read_u32(1)? == 1)
+    }
+
+    /// Reads `chars` 6-bit ASCII characters, trimming the `@` padding and any
+    /// surrounding spaces
+    pub fn read_string(&mut self, chars: usize) -> Result<AsciiString> {
+        self.run(|input| parse_6bit_ascii(input, chars * 6))
+    }
+
+    /// Moves past `bits` bits, such as spare or reserved fields
+    pub fn skip(&mut self, bits: usize) -> Result<()> {
+        if bits > self.remaining() {
+            return Err("Not enough data to skip".into());
+        }
+        let offset = self.input.1 + bits;
+        self.input = (&self.input.0[offset / 8..], offset % 8);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::unarmor;
+
+    #[test]
+    fn read_position_report() {
+        let data = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.remaining(), 168);
+        assert_eq!(reader.read_u32(6).unwrap(), 1);
+        assert_eq!(reader.read_u32(2).unwrap(), 0);
+        assert_eq!(reader.read_u32(30).unwrap(), 265547250);
+        assert_eq!(reader.read_u32(4).unwrap(), 0);
+        // Rate of turn is negative, turning to port
+        assert_eq!(reader.read_i32(8).unwrap(), -8);
+        assert_eq!(reader.read_u32(10).unwrap(), 139);
+        assert!(!reader.read_bool().unwrap());
+        assert_eq!(reader.position(), 61);
+        reader.skip(28 + 27).unwrap();
+        assert_eq!(reader.read_u32(12).unwrap(), 404);
+        assert_eq!(reader.read_u32(9).unwrap(), 41);
+        assert_eq!(reader.position(), 137);
+        assert_eq!(reader.remaining(), 31);
+    }
+
+    #[test]
+    fn read_string() {
+        let data = unarmor(b"E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100", 0).unwrap();
+        let mut reader = BitReader::new(&data);
+        reader.skip(43).unwrap();
+        assert_eq!(reader.read_string(20).unwrap(), "SF OAK BAY BR VAIS E");
+    }
+
+    #[test]
+    fn read_past_end() {
+        let mut reader = BitReader::new(&[0xff]);
+        assert_eq!(reader.read_u32(4).unwrap(), 15);
+        assert!(reader.read_u32(5).is_err());
+        assert!(reader.skip(5).is_err());
+        assert_eq!(reader.position(), 4);
+        assert_eq!(reader.read_i32(4).unwrap(), -1);
+        assert!(reader.read_u32(33).is_err());
+        assert!(reader.read_bool().is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Encoding structured messages back into armored AIS data
+use super::navigation::{Accuracy, ManeuverIndicator};
+use super::position_report::{NavigationStatus, PositionReport};
+use super::radio_status::{RadioStatus, SubMessage, SyncState};
+use super::static_and_voyage_related_data::StaticAndVoyageRelatedData;
+use super::types::{Dte, EpfdType};
+use super::AisMessage;
+use crate::errors::Result;
+use crate::lib::std::{format, string::String, vec::Vec};
+
+/// Stands in for any character which has no 6-bit ASCII equivalent
+const PLACEHOLDER_CHAR: u8 = b'?';
+
+/// Accumulates values of arbitrary bit widths, most significant bit first
+#[derive(Debug, Default)]
+pub(crate) struct BitWriter {
+    data: Vec<u8>,
+    bit_count: usize,
+}
+
+impl BitWriter {
+    /// Writes the lowest `len` bits of `value`
+    pub fn write(&mut self, value: u64, len: usize) {
+        for bit in (0..len).rev() {
+            if self.bit_count.is_multiple_of(8) {
+                self.data.push(0);
+            }
+            if (value >> bit) & 1 == 1 {
+                self.data[self.bit_count / 8] |= 0x80 >> (self.bit_count % 8);
+            }
+            self.bit_count += 1;
+        }
+    }
+
+    /// Writes `value` as a `len`-bit two's complement number
+    pub fn write_signed(&mut self, value: i64, len: usize) {
+        self.write(value as u64, len);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write(value as u64, 1);
+    }
+
+    /// Writes `text` as `chars` 6-bit ASCII characters, truncating it or padding
+    /// it with `@` as needed. Lowercase letters are converted to uppercase, and
+    /// anything else which 6-bit ASCII can't represent becomes `?`.
+    pub fn write_6bit_ascii(&mut self, text: &str, chars: usize) {
+        let mut bytes = text.bytes();
+        for _ in 0..chars {
+            let sixbit = match bytes.next().map(|byte| byte.to_ascii_uppercase()) {
+                None => 0,
+                Some(byte @ 64..=95) => byte - 64,
+                Some(byte @ 32..=63) => byte,
+                Some(_) => PLACEHOLDER_CHAR,
+            };
+            self.write(sixbit as u64, 6);
+        }
+    }
+
+    /// Armors everything written so far; see [`armor()`](fn.armor.html)
+    pub fn armor(&self) -> (String, usize) {
+        armor(&self.data, self.bit_count)
+    }
+}
+
+/// Converts packed 6-bit (unarmored) data into 8-bit ASCII (armored). This is the
+/// inverse of [`unarmor()`](fn.unarmor.html).
+///
+/// Only the first `bit_count` bits of `data` are used. Returns the armored
+/// payload, along with the number of fill bits that were needed to pad it out
+/// to a 6-bit boundary.
+pub fn armor(data: &[u8], bit_count: usize) -> (String, usize) {
+    let bit_count = bit_count.min(data.len() * 8);
+    let fill_bits = (6 - bit_count % 6) % 6;
+    let mut output = String::with_capacity((bit_count + fill_bits) / 6);
+    for offset in (0..bit_count).step_by(6) {
+        let mut sixbit = 0u8;
+        for bit in offset..offset + 6 {
+            sixbit <<= 1;
+            if bit < bit_count && data[bit / 8] & (0x80 >> (bit % 8)) != 0 {
+                sixbit |= 1;
+            }
+        }
+        output.push(match sixbit {
+            0..=39 => sixbit + 48,
+            _ => sixbit + 56,
+        } as char);
+    }
+    (output, fill_bits)
+}
+
+/// Encodes a structured message into an armored payload, along with its fill bit
+/// count, ready to be placed in an NMEA sentence.
+///
+/// Returns an error for message types which can't be encoded yet.
+pub fn encode(message: &AisMessage) -> Result<(String, usize)> {
+    let mut writer = BitWriter::default();
+    match message {
+        AisMessage::PositionReport(report) => encode_position_report(&mut writer, report),
+        AisMessage::StaticAndVoyageRelatedData(report) => {
+            encode_static_and_voyage_related_data(&mut writer, report)
+        }
+        _ => return Err(format!("Encoding not supported for {:?}", message).into()),
+    }
+    Ok(writer.armor())
+}
+
+/// Rounds to the nearest integer, away from zero; `f64::round()` needs `std`
+fn round(value: f64) -> i64 {
+    if value < 0.0 {
+        (value - 0.5) as i64
+    } else {
+        (value + 0.5) as i64
+    }
+}
+
+fn encode_scaled(writer: &mut BitWriter, value: Option<f32>, scale: f64, missing: i64, len: usize) {
+    let raw = value.map_or(missing, |value| round(value as f64 * scale));
+    writer.write_signed(raw, len);
+}
+
+fn encode_sync_state(writer: &mut BitWriter, sync_state: SyncState) {
+    let raw = match sync_state {
+        SyncState::UtcDirect => 0,
+        SyncState::UtcIndirect => 1,
+        SyncState::BaseStation => 2,
+        SyncState::NumberOfReceivedStations => 3,
+        SyncState::Unknown(raw) => raw,
+    };
+    writer.write(raw as u64, 2);
+}
+
+fn encode_radio_status(writer: &mut BitWriter, radio_status: &RadioStatus) {
+    match radio_status {
+        RadioStatus::Sotdma(sotdma) => {
+            encode_sync_state(writer, sotdma.sync_state);
+            writer.write(sotdma.slot_timeout as u64, 3);
+            match sotdma.sub_message {
+                SubMessage::SlotOffset(offset) => writer.write_signed(offset as i64, 14),
+                SubMessage::UtcHourAndMinute(hour, minute) => {
+                    writer.write(hour as u64, 5);
+                    writer.write(0, 1);
+                    writer.write(minute as u64, 6);
+                    writer.write(0, 2);
+                }
+                SubMessage::SlotNumber(value) | SubMessage::ReceivedStations(value) => {
+                    writer.write(value as u64, 14)
+                }
+            }
+        }
+        RadioStatus::Itdma(itdma) => {
+            encode_sync_state(writer, itdma.sync_state);
+            writer.write_signed(itdma.slot_increment as i64, 13);
+            writer.write(itdma.num_slots as u64, 3);
+            writer.write_bool(itdma.keep);
+        }
+    }
+}
+
+fn encode_navigation_status(status: NavigationStatus) -> u64 {
+    match status {
+        NavigationStatus::UnderWayUsingEngine => 0,
+        NavigationStatus::AtAnchor => 1,
+        NavigationStatus::NotUnderCommand => 2,
+        NavigationStatus::RestrictedManouverability => 3,
+        NavigationStatus::ConstrainedByDraught => 4,
+        NavigationStatus::Moored => 5,
+        NavigationStatus::Aground => 6,
+        NavigationStatus::EngagedInFishing => 7,
+        NavigationStatus::UnderWaySailing => 8,
+        NavigationStatus::ReservedForHSC => 9,
+        NavigationStatus::ReservedForWIG => 10,
+        NavigationStatus::AisSartIsActive => 14,
+        NavigationStatus::NotDefined => 15,
+        NavigationStatus::Reserved(raw) => raw as u64,
+    }
+}
+
+fn encode_accuracy(writer: &mut BitWriter, accuracy: Accuracy) {
+    writer.write_bool(accuracy == Accuracy::Dgps);
+}
+
+fn encode_position_report(writer: &mut BitWriter, report: &PositionReport) {
+    writer.write(report.message_type as u64, 6);
+    writer.write(report.repeat_indicator as u64, 2);
+    writer.write(report.mmsi as u64, 30);
+    writer.write(encode_navigation_status(report.navigation_status), 4);
+    let rate_of_turn = report.rate_of_turn.map_or(-128, |rate| rate.raw());
+    writer.write_signed(rate_of_turn as i64, 8);
+    encode_scaled(writer, report.speed_over_ground, 10.0, 1023, 10);
+    encode_accuracy(writer, report.position_accuracy);
+    match report.coordinate {
+        // At full precision, so a decoded position is sent back out unchanged
+        Some(coordinate) => {
+            writer.write_signed(round(coordinate.longitude * 600_000.0), 28);
+            writer.write_signed(round(coordinate.latitude * 600_000.0), 27);
+        }
+        None => {
+            encode_scaled(writer, report.longitude, 600_000.0, 108_600_000, 28);
+            encode_scaled(writer, report.latitude, 600_000.0, 54_600_000, 27);
+        }
+    }
+    encode_scaled(writer, report.course_over_ground, 10.0, 3600, 12);
+    writer.write(report.true_heading.unwrap_or(511) as u64, 9);
+    writer.write(report.timestamp as u64, 6);
+    let maneuver_indicator = match report.maneuver_indicator {
+        None => 0,
+        Some(ManeuverIndicator::NoSpecialManeuver) => 1,
+        Some(ManeuverIndicator::SpecialManeuver) => 2,
+        Some(ManeuverIndicator::Unknown(raw)) => raw,
+    };
+    writer.write(maneuver_indicator as u64, 2);
+    writer.write(0, 3);
+    writer.write_bool(report.raim);
+    encode_radio_status(writer, &report.radio_status);
+}
+
+fn encode_epfd_type(epfd_type: Option<EpfdType>) -> u64 {
+    match epfd_type {
+        None => 0,
+        Some(EpfdType::Gps) => 1,
+        Some(EpfdType::Glonass) => 2,
+        Some(EpfdType::CombinedGpsAndGlonass) => 3,
+        Some(EpfdType::LoranC) => 4,
+        Some(EpfdType::Chayka) => 5,
+        Some(EpfdType::IntegratedNavigationSystem) => 6,
+        Some(EpfdType::Surveyed) => 7,
+        Some(EpfdType::Galileo) => 8,
+        Some(EpfdType::Unknown(raw)) => raw as u64,
+    }
+}
+
+fn encode_static_and_voyage_related_data(
+    writer: &mut BitWriter,
+    report: &StaticAndVoyageRelatedData,
+) {
+    writer.write(report.message_type as u64, 6);
+    writer.write(report.repeat_indicator as u64, 2);
+    writer.write(report.mmsi as u64, 30);
+    writer.write(report.ais_version as u64, 2);
+    writer.write(report.imo_number as u64, 30);
+    writer.write_6bit_ascii(&report.callsign, 7);
+    writer.write_6bit_ascii(&report.vessel_name, 20);
+    writer.write(report.ship_type.map_or(0, u8::from) as u64, 8);
+    writer.write(report.dimension_to_bow as u64, 9);
+    writer.write(report.dimension_to_stern as u64, 9);
+    writer.write(report.dimension_to_port as u64, 6);
+    writer.write(report.dimension_to_starboard as u64, 6);
+    writer.write(encode_epfd_type(report.epfd_type), 4);
+    writer.write(report.eta_month_utc.unwrap_or(0) as u64, 4);
+    writer.write(report.eta_day_utc.unwrap_or(0) as u64, 5);
+    writer.write(report.eta_hour_utc as u64, 5);
+    writer.write(report.eta_minute_utc.unwrap_or(60) as u64, 6);
+    writer.write(round(report.draught as f64 * 10.0) as u64, 8);
+    writer.write_6bit_ascii(&report.destination, 20);
+    writer.write_bool(report.dte == Dte::NotReady);
+    writer.write(0, 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{parse, unarmor};
+
+    #[test]
+    fn armor_round_trip() {
+        for (payload, fill_bits) in [("P", 4), ("9qKr", 0), ("E>kb9O9aS@7PUh", 4), ("", 0)] {
+            let bitstream = unarmor(payload.as_bytes(), fill_bits).unwrap();
+            let bit_count = payload.len() * 6 - fill_bits;
+            assert_eq!(armor(&bitstream, bit_count), (payload.into(), fill_bits));
+        }
+    }
+
+    #[test]
+    fn encode_position_reports() {
+        for payload in [
+            &b"13u?etPv2;0n:dDPwUM1U1Cb069D"[..],
+            b"16SteH0P00Jt63hHaa6SagvJ087r",
+            b"38Id705000rRVJhE7cl9n;160000",
+            b"33nQ:B50000FiEBRjpcK19qSR>`<",
+        ] {
+            let message = parse(&unarmor(payload, 0).unwrap()).unwrap();
+            let (encoded, fill_bits) = encode(&message).unwrap();
+            assert_eq!(fill_bits, 0);
+            // The position is kept at full precision, so nothing changes
+            assert_eq!(encoded.as_bytes(), payload);
+            let encoded = unarmor(encoded.as_bytes(), fill_bits).unwrap();
+            assert_eq!(parse(&encoded).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn encode_static_and_voyage_related_data() {
+        for (payload, fill_bits) in [
+            (
+                &b"53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP000000000000"[..],
+                2,
+            ),
+            (
+                b"5341U9`00000uCGCKL0u=@T4000000000000001?<@<47u;b004Sm51DQ0C@",
+                0,
+            ),
+        ] {
+            let message = parse(&unarmor(payload, fill_bits).unwrap()).unwrap();
+            let (encoded, fill_bits) = encode(&message).unwrap();
+            assert_eq!((encoded.len(), fill_bits), (71, 2));
+            let encoded = unarmor(encoded.as_bytes(), fill_bits).unwrap();
+            assert_eq!(parse(&encoded).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn write_6bit_ascii() {
+        let mut writer = BitWriter::default();
+        writer.write_6bit_ascii("Ab~", 4);
+        assert_eq!(writer.armor(), ("12w0".into(), 0));
+    }
+
+    #[test]
+    fn encode_unsupported() {
+        let bitstream = unarmor(b"403OtVAv6s5l1o?I``E`4I?02<34", 0).unwrap();
+        assert!(encode(&parse(&bitstream).unwrap()).is_err());
+    }
+}
@@ -20,6 +20,8 @@ pub struct ExtendedClassBPositionReport {
     pub position_accuracy: Accuracy,
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
     pub course_over_ground: Option<f32>,
     pub true_heading: Option<u16>,
     pub timestamp: u8,
@@ -35,6 +37,13 @@ pub struct ExtendedClassBPositionReport {
     pub assigned_mode: AssignedMode,
 }
 
+impl ExtendedClassBPositionReport {
+    /// Interprets the `timestamp` field, which can also carry the state of the positioning system
+    pub fn timestamp_status(&self) -> Timestamp {
+        Timestamp::parse(self.timestamp)
+    }
+}
+
 impl<'a> AisMessageType<'a> for ExtendedClassBPositionReport {
     fn name(&self) -> &'static str {
         "Extended Class B Position Report"
@@ -54,8 +63,8 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], ExtendedClassBPositionReport> {
         let (data, _regional_reserved) = take_bits::<_, u8, _, _>(8u8)(data)?;
         let (data, speed_over_ground) = map(take_bits(10u16), parse_speed_over_ground)(data)?;
         let (data, position_accuracy) = map(take_bits(1u8), Accuracy::parse)(data)?;
-        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
-        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 28)?;
+        let (data, raw_latitude) = signed_i32(data, 27)?;
         let (data, course_over_ground) = map(take_bits(12u16), parse_cog)(data)?;
         let (data, true_heading) = map(take_bits(9u16), parse_heading)(data)?;
         let (data, timestamp) = take_bits(6u8)(data)?;
@@ -79,8 +88,9 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], ExtendedClassBPositionReport> {
                 mmsi,
                 speed_over_ground,
                 position_accuracy,
-                longitude,
-                latitude,
+                longitude: parse_longitude(raw_longitude),
+                latitude: parse_latitude(raw_latitude),
+                coordinate: Coordinate::new(raw_latitude, raw_longitude),
                 course_over_ground,
                 true_heading,
                 timestamp,
@@ -131,4 +141,33 @@ mod tests {
         assert_eq!(report.dte, Dte::NotReady);
         assert_eq!(report.assigned_mode, AssignedMode::Autonomous);
     }
+
+    #[test]
+    fn test_epfd_and_dte() {
+        let bitstream = pack_bits(&[
+            (19, 6),
+            (0, 2),
+            (413954782, 30),
+            (0, 8),
+            (0, 10),
+            (0, 1),
+            (0, 28),
+            (0, 27),
+            (0, 12),
+            (511, 9),
+            (60, 6),
+            (0, 4),
+            (0, 120),
+            (70, 8),
+            (0, 30),
+            (1, 4),
+            (0, 1),
+            (0, 1),
+            (0, 1),
+            (0, 4),
+        ]);
+        let report = ExtendedClassBPositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.epfd_type, Some(EpfdType::Gps));
+        assert_eq!(report.dte, Dte::Ready);
+    }
 }
@@ -0,0 +1,93 @@
+//! Extended Ship Static and Voyage Related Data, broadcast (type 8, DAC 1, FI 24)
+use super::bit_reader::BitReader;
+use super::parsers::AsciiString;
+use crate::errors::Result;
+
+/// Static and voyage data beyond what fits in a type 5 report, as used for
+/// bridge and power line clearance
+///
+/// Only the fields at the start of the message are decoded. The SOLAS equipment
+/// status, fuel and tonnage fields which follow them aren't.
+#[derive(Debug, PartialEq)]
+pub struct ExtendedShipStaticData {
+    /// Ties this message to related messages
+    pub message_linkage_id: u16,
+    /// Height from the waterline to the highest point of the vessel, in metres.
+    /// `None` if not available. 819.1 means 819.1m or more.
+    pub air_draught: Option<f32>,
+    /// UN/LOCODE of the last port of call
+    pub last_port_of_call: AsciiString,
+    /// UN/LOCODE of the next port of call
+    pub next_port_of_call: AsciiString,
+    /// UN/LOCODE of the port of call after the next one
+    pub second_port_of_call: AsciiString,
+}
+
+impl ExtendedShipStaticData {
+    /// Parses the binary payload of a DAC 1, FI 24 binary broadcast message
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(data);
+        let message_linkage_id = reader.read_u32(10)? as u16;
+        let air_draught = match reader.read_u32(13)? {
+            0 => None,
+            decimetres => Some(decimetres as f32 / 10.0),
+        };
+        Ok(Self {
+            message_linkage_id,
+            air_draught,
+            last_port_of_call: reader.read_string(5)?,
+            next_port_of_call: reader.read_string(5)?,
+            second_port_of_call: reader.read_string(5)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary_broadcast_message::{
+        BinaryBroadcastMessage, BroadcastApplicationData,
+    };
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    /// Packs a UN/LOCODE into 6-bit ASCII
+    fn locode(code: &[u8; 5]) -> u64 {
+        code.iter()
+            .fold(0, |packed, &char| (packed << 6) | (char as u64 & 0x3f))
+    }
+
+    #[test]
+    fn test_air_draught() {
+        let bitstream = pack_bits(&[
+            (8, 6),
+            (0, 2),
+            (244660000, 30),
+            (0, 2),
+            (1, 10),
+            (24, 6),
+            (17, 10),
+            (325, 13),
+            (locode(b"NLRTM"), 30),
+            (locode(b"DEHAM"), 30),
+            (0, 30),
+            // SOLAS status and the rest
+            (0, 26),
+        ]);
+        let message = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        let data = match message.application_data().unwrap() {
+            Some(BroadcastApplicationData::ExtendedShipStaticData(data)) => data,
+            data => panic!("Expected extended static data, got {:?}", data),
+        };
+        assert_eq!(data.message_linkage_id, 17);
+        assert_eq!(data.air_draught, Some(32.5));
+        assert_eq!(data.last_port_of_call, "NLRTM");
+        assert_eq!(data.next_port_of_call, "DEHAM");
+        assert_eq!(data.second_port_of_call, "");
+
+        let data = ExtendedShipStaticData::parse(&[0; 15]).unwrap();
+        assert_eq!(data.air_draught, None);
+        assert!(ExtendedShipStaticData::parse(&[0; 4]).is_err());
+    }
+}
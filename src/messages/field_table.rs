@@ -0,0 +1,353 @@
+//! Label/value rendering of decoded messages, for display
+use super::aid_to_navigation_report::NavaidType;
+use super::binary_broadcast_message::MessageData;
+use super::data_link_management_message::SlotReservationList;
+use super::dgnss_broadcast_binary_message::DifferentialCorrectionData;
+use super::interrogation::StationList;
+use super::navigation::{Accuracy, ManeuverIndicator, RateOfTurn};
+use super::position_report::NavigationStatus;
+use super::radio_status::RadioStatus;
+use super::standard_aircraft_position_report::AltitudeSensor;
+use super::standard_class_b_position_report::CarrierSense;
+use super::static_data_report::MessagePart;
+use super::types::{AssignedMode, Dte, EpfdType, ShipType};
+use super::AisMessage;
+use crate::lib::std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// What gets displayed for fields whose value is unavailable
+const NOT_AVAILABLE: &str = "n/a";
+
+/// A value which can be shown in a field table
+trait FieldValue {
+    fn render(&self) -> String;
+}
+
+impl<T: FieldValue> FieldValue for Option<T> {
+    fn render(&self) -> String {
+        match self {
+            Some(value) => value.render(),
+            None => NOT_AVAILABLE.to_string(),
+        }
+    }
+}
+
+macro_rules! display_value {
+    ($($ty:ty),*) => {
+        $(impl FieldValue for $ty {
+            fn render(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+macro_rules! debug_value {
+    ($($ty:ty),*) => {
+        $(impl FieldValue for $ty {
+            fn render(&self) -> String {
+                format!("{:?}", self)
+            }
+        })*
+    };
+}
+
+display_value!(u8, u16, u32, i32, f32, bool, String);
+debug_value!(
+    NavigationStatus,
+    Accuracy,
+    ManeuverIndicator,
+    RadioStatus,
+    ShipType,
+    EpfdType,
+    Dte,
+    AssignedMode,
+    CarrierSense,
+    NavaidType,
+    MessagePart,
+    SlotReservationList,
+    StationList,
+    DifferentialCorrectionData,
+    AltitudeSensor
+);
+
+impl FieldValue for RateOfTurn {
+    fn render(&self) -> String {
+        // The direction is shown separately, so only show the size of the rate
+        let rate = self
+            .rate()
+            .map(|rate| if rate < 0.0 { -rate } else { rate });
+        match (rate, self.direction()) {
+            (Some(rate), Some(direction)) => format!("{:.1}°/min {:?}", rate, direction),
+            (Some(rate), None) => format!("{:.1}°/min", rate),
+            (None, Some(direction)) => format!("{:?}", direction),
+            (None, None) => NOT_AVAILABLE.to_string(),
+        }
+    }
+}
+
+impl FieldValue for MessageData {
+    fn render(&self) -> String {
+        self.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+macro_rules! field_table {
+    ($message:expr, $($label:literal => $field:ident),* $(,)?) => {
+        Vec::from([
+            $(($label.to_string(), $message.$field.render())),*
+        ])
+    };
+}
+
+impl AisMessage {
+    /// Lists the fields of this message as `(label, value)` pairs, in transmission
+    /// order, for display. Fields whose value is unavailable are shown as `n/a`.
+    pub fn to_field_table(&self) -> Vec<(String, String)> {
+        match self {
+            Self::PositionReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Navigation status" => navigation_status,
+                "Rate of turn" => rate_of_turn,
+                "Speed over ground" => speed_over_ground,
+                "Position accuracy" => position_accuracy,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Course over ground" => course_over_ground,
+                "True heading" => true_heading,
+                "Timestamp" => timestamp,
+                "Maneuver indicator" => maneuver_indicator,
+                "RAIM" => raim,
+                "Radio status" => radio_status,
+            ),
+            Self::BaseStationReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Year" => year,
+                "Month" => month,
+                "Day" => day,
+                "Hour" => hour,
+                "Minute" => minute,
+                "Second" => second,
+                "Fix quality" => fix_quality,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "EPFD type" => epfd_type,
+                "RAIM" => raim,
+                "Radio status" => radio_status,
+            ),
+            Self::BinaryBroadcastMessage(message) => field_table!(message,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "DAC" => dac,
+                "FI" => fid,
+                "Data" => data,
+            ),
+            Self::Interrogation(message) => field_table!(message,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Stations" => stations,
+            ),
+            Self::StaticAndVoyageRelatedData(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "AIS version" => ais_version,
+                "IMO number" => imo_number,
+                "Callsign" => callsign,
+                "Vessel name" => vessel_name,
+                "Ship type" => ship_type,
+                "Dimension to bow" => dimension_to_bow,
+                "Dimension to stern" => dimension_to_stern,
+                "Dimension to port" => dimension_to_port,
+                "Dimension to starboard" => dimension_to_starboard,
+                "EPFD type" => epfd_type,
+                "ETA month (UTC)" => eta_month_utc,
+                "ETA day (UTC)" => eta_day_utc,
+                "ETA hour (UTC)" => eta_hour_utc,
+                "ETA minute (UTC)" => eta_minute_utc,
+                "Draught" => draught,
+                "Destination" => destination,
+                "DTE" => dte,
+            ),
+            Self::DgnssBroadcastBinaryMessage(message) => field_table!(message,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Payload" => payload,
+            ),
+            Self::StandardClassBPositionReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Speed over ground" => speed_over_ground,
+                "Position accuracy" => position_accuracy,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Course over ground" => course_over_ground,
+                "True heading" => true_heading,
+                "Timestamp" => timestamp,
+                "CS unit" => cs_unit,
+                "Has display" => has_display,
+                "Has DSC" => has_dsc,
+                "Whole band" => whole_band,
+                "Accepts message 22" => accepts_message_22,
+                "Assigned mode" => assigned_mode,
+                "RAIM" => raim,
+                "Radio status" => radio_status,
+            ),
+            Self::ExtendedClassBPositionReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Speed over ground" => speed_over_ground,
+                "Position accuracy" => position_accuracy,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Course over ground" => course_over_ground,
+                "True heading" => true_heading,
+                "Timestamp" => timestamp,
+                "Name" => name,
+                "Ship type" => type_of_ship_and_cargo,
+                "Dimension to bow" => dimension_to_bow,
+                "Dimension to stern" => dimension_to_stern,
+                "Dimension to port" => dimension_to_port,
+                "Dimension to starboard" => dimension_to_starboard,
+                "EPFD type" => epfd_type,
+                "RAIM" => raim,
+                "DTE" => dte,
+                "Assigned mode" => assigned_mode,
+            ),
+            Self::DataLinkManagementMessage(message) => field_table!(message,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Reservations" => reservations,
+            ),
+            Self::AidToNavigationReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Aid type" => aid_type,
+                "Name" => name,
+                "Position accuracy" => accuracy,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Dimension to bow" => dimension_to_bow,
+                "Dimension to stern" => dimension_to_stern,
+                "Dimension to port" => dimension_to_port,
+                "Dimension to starboard" => dimension_to_starboard,
+                "EPFD type" => epfd_type,
+                "UTC second" => utc_second,
+                "Off position" => off_position,
+                "Regional reserved" => regional_reserved,
+                "RAIM" => raim,
+                "Virtual aid" => virtual_aid,
+                "Assigned mode" => assigned_mode,
+            ),
+            Self::StaticDataReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Message part" => message_part,
+            ),
+            Self::UtcDateResponse(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Year" => year,
+                "Month" => month,
+                "Day" => day,
+                "Hour" => hour,
+                "Minute" => minute,
+                "Second" => second,
+                "Fix quality" => fix_quality,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "EPFD type" => epfd_type,
+                "RAIM" => raim,
+                "Radio status" => radio_status,
+            ),
+            Self::StandardAircraftPositionReport(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Altitude" => altitude,
+                "Speed over ground" => speed_over_ground,
+                "Position accuracy" => position_accuracy,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Course over ground" => course_over_ground,
+                "Timestamp" => timestamp,
+                "Altitude sensor" => altitude_sensor,
+                "DTE" => dte,
+                "Assigned mode" => assigned_mode,
+                "RAIM" => raim,
+                "Radio status" => radio_status,
+            ),
+            Self::AddressedBinaryMessage(message) => field_table!(message,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Sequence number" => sequence_number,
+                "Destination MMSI" => dest_mmsi,
+                "Retransmit" => retransmit,
+                "DAC" => dac,
+                "FI" => fid,
+                "Data" => data,
+            ),
+            Self::LongRangeBroadcastMessage(report) => field_table!(report,
+                "Message type" => message_type,
+                "Repeat indicator" => repeat_indicator,
+                "MMSI" => mmsi,
+                "Position accuracy" => position_accuracy,
+                "RAIM" => raim,
+                "Navigation status" => navigation_status,
+                "Longitude" => longitude,
+                "Latitude" => latitude,
+                "Speed over ground" => speed_over_ground,
+                "Course over ground" => course_over_ground,
+                "Position latency" => position_latency,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::parse;
+
+    fn row<'a>(table: &'a [(String, String)], label: &str) -> &'a str {
+        &table
+            .iter()
+            .find(|(row_label, _)| row_label == label)
+            .unwrap_or_else(|| panic!("No row labelled {}", label))
+            .1
+    }
+
+    #[test]
+    fn test_type5_table() {
+        let bytestream = b"5341U9`00000uCGCKL0u=@T4000000000000001?<@<47u;b004Sm51DQ0C@";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let table = parse(&bitstream).unwrap().to_field_table();
+        assert_eq!(table.len(), 20);
+        assert_eq!(table[0], ("Message type".to_string(), "5".to_string()));
+        assert_eq!(row(&table, "MMSI"), "205546790");
+        assert_eq!(row(&table, "Callsign"), "OT5467");
+        assert_eq!(row(&table, "Ship type"), "CargoNoAdditionalInformation");
+        assert_eq!(row(&table, "ETA month (UTC)"), "4");
+        assert_eq!(row(&table, "Destination"), "ROTTERDAM");
+        assert_eq!(row(&table, "EPFD type"), "n/a");
+    }
+}
@@ -0,0 +1,163 @@
+//! GeoJSON rendering of position reports, for quick visualization
+use super::navigation::{Coordinate, PositionView};
+use super::AisMessage;
+use crate::lib::std::fmt::Write;
+use crate::lib::std::string::String;
+
+/// Writes `value` as a JSON number, or `null` if it is unavailable
+fn write_number<T: crate::lib::std::fmt::Display>(output: &mut String, value: Option<T>) {
+    // Writing to a String can't fail
+    match value {
+        Some(value) => write!(output, "{}", value).unwrap(),
+        None => output.push_str("null"),
+    }
+}
+
+impl AisMessage {
+    /// Formats the position in this message as a GeoJSON `Feature` with a `Point`
+    /// geometry. The MMSI, speed and course over ground, and true heading go in
+    /// its properties, as `null` where unavailable.
+    ///
+    /// As well as position reports, this covers the fixed positions of base
+    /// stations (types 4 and 11) and aids to navigation (type 21), which have no
+    /// speed, course or heading. Returns `None` for messages which have no
+    /// position, or whose position is unavailable.
+    ///
+    /// # Example:
+    /// ```
+    /// use ais::messages::{parse, unarmor};
+    ///
+    /// let message = parse(&unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0)?)?;
+    /// assert_eq!(
+    ///     message.to_geojson().unwrap(),
+    ///     r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[11.832976,57.660355]},"properties":{"mmsi":265547250,"sog":13.9,"cog":40.4,"heading":41}}"#
+    /// );
+    /// # Ok::<(), ais::errors::Error>(())
+    /// ```
+    pub fn to_geojson(&self) -> Option<String> {
+        let position = match self {
+            Self::BaseStationReport(report) => fixed_position(
+                report.mmsi,
+                report.latitude,
+                report.longitude,
+                report.coordinate,
+            ),
+            Self::UtcDateResponse(response) => fixed_position(
+                response.mmsi,
+                response.latitude,
+                response.longitude,
+                response.coordinate,
+            ),
+            Self::AidToNavigationReport(report) => fixed_position(
+                report.mmsi,
+                report.latitude,
+                report.longitude,
+                report.coordinate,
+            ),
+            _ => self.as_position()?,
+        };
+        position.coordinate?;
+        let mut feature = String::new();
+        write!(
+            feature,
+            r#"{{"type":"Feature","geometry":{{"type":"Point","coordinates":[{},{}]}},"properties":{{"mmsi":{},"sog":"#,
+            position.longitude?, position.latitude?, position.mmsi
+        )
+        .unwrap();
+        write_number(&mut feature, position.speed_over_ground);
+        feature.push_str(r#","cog":"#);
+        write_number(&mut feature, position.course_over_ground);
+        feature.push_str(r#","heading":"#);
+        write_number(&mut feature, position.true_heading);
+        feature.push_str("}}");
+        Some(feature)
+    }
+}
+
+/// The position of a station which doesn't move, so has no speed, course or heading
+fn fixed_position(
+    mmsi: u32,
+    latitude: Option<f32>,
+    longitude: Option<f32>,
+    coordinate: Option<Coordinate>,
+) -> PositionView {
+    PositionView {
+        mmsi,
+        latitude,
+        longitude,
+        coordinate,
+        speed_over_ground: None,
+        course_over_ground: None,
+        true_heading: None,
+        timestamp: 60,
+    }
+}
+
+/// Collects the features of the messages which have a position, as given by
+/// [`AisMessage::to_geojson()`](enum.AisMessage.html#method.to_geojson), into a
+/// GeoJSON `FeatureCollection`
+pub fn geojson_feature_collection<'a, I>(messages: I) -> String
+where
+    I: IntoIterator<Item = &'a AisMessage>,
+{
+    let mut collection = String::from(r#"{"type":"FeatureCollection","features":["#);
+    for (idx, feature) in messages
+        .into_iter()
+        .filter_map(AisMessage::to_geojson)
+        .enumerate()
+    {
+        if idx > 0 {
+            collection.push(',');
+        }
+        collection.push_str(&feature);
+    }
+    collection.push_str("]}");
+    collection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{parse, unarmor};
+
+    #[test]
+    fn feature_collection() {
+        let messages = [
+            (&b"13u?etPv2;0n:dDPwUM1U1Cb069D"[..], 0),
+            (b"B6:hQDh0029Pt<4TAS003h6TSP00", 0),
+            (b"403OtVAv6s5l1o?I``E`4I?02<34", 0),
+            (b"E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100", 0),
+            (
+                b"53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP0000000",
+                2,
+            ),
+        ]
+        .map(|(payload, fill_bits)| parse(&unarmor(payload, fill_bits).unwrap()).unwrap());
+        // Base stations have a position, but no speed, course or heading
+        assert_eq!(
+            messages[2].to_geojson().unwrap(),
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[-122.46483,37.7943]},"properties":{"mmsi":3669145,"sog":null,"cog":null,"heading":null}}"#
+        );
+        // Static data has no position
+        assert_eq!(messages[4].to_geojson(), None);
+        assert_eq!(
+            geojson_feature_collection(&messages),
+            concat!(
+                r#"{"type":"FeatureCollection","features":["#,
+                r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[11.832976,57.660355]},"#,
+                r#""properties":{"mmsi":265547250,"sog":13.9,"cog":40.4,"heading":41}},"#,
+                r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[120.16217,31.924133]},"#,
+                r#""properties":{"mmsi":413933907,"sog":0,"cog":0,"heading":480}},"#,
+                r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[-122.46483,37.7943]},"#,
+                r#""properties":{"mmsi":3669145,"sog":null,"cog":null,"heading":null}},"#,
+                r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[-122.369865,37.80562]},"#,
+                r#""properties":{"mmsi":993692028,"sog":null,"cog":null,"heading":null}}"#,
+                "]}"
+            )
+        );
+        assert_eq!(
+            geojson_feature_collection(&[]),
+            r#"{"type":"FeatureCollection","features":[]}"#
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Inland ETA at Lock/Bridge/Terminal (type 6, DAC 200, FI 21)
+use super::parsers::*;
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Identifies a lock, bridge or terminal on an inland waterway
+#[derive(Debug, PartialEq, Eq)]
+pub struct InlandLocation {
+    /// UN country code
+    pub country: AsciiString,
+    /// UN/LOCODE location
+    pub location: AsciiString,
+    pub fairway_section: AsciiString,
+    pub terminal: AsciiString,
+    pub fairway_hectometre: AsciiString,
+}
+
+impl InlandLocation {
+    pub(crate) fn parse(data: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        let (data, country) = parse_6bit_ascii(data, 12)?;
+        let (data, location) = parse_6bit_ascii(data, 18)?;
+        let (data, fairway_section) = parse_6bit_ascii(data, 30)?;
+        let (data, terminal) = parse_6bit_ascii(data, 30)?;
+        let (data, fairway_hectometre) = parse_6bit_ascii(data, 30)?;
+        Ok((
+            data,
+            Self {
+                country,
+                location,
+                fairway_section,
+                terminal,
+                fairway_hectometre,
+            },
+        ))
+    }
+}
+
+/// Estimated time of arrival at a lock, bridge or terminal, sent by a vessel
+#[derive(Debug, PartialEq)]
+pub struct InlandEta {
+    pub location: InlandLocation,
+    pub eta_month_utc: Option<u8>,
+    pub eta_day_utc: Option<u8>,
+    pub eta_hour_utc: u8,
+    pub eta_minute_utc: Option<u8>,
+    pub assisting_tugboats: Option<u8>,
+    /// Air draught, in metres
+    pub air_draught: Option<f32>,
+}
+
+impl InlandEta {
+    /// Parses the binary payload of a DAC 200, FI 21 addressed binary message
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, eta) = parse_message(data)?;
+        Ok(eta)
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], InlandEta> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, location) = InlandLocation::parse(data)?;
+        let (data, eta_month_utc) = parse_month(data)?;
+        let (data, eta_day_utc) = parse_day(data)?;
+        let (data, eta_hour_utc) = parse_hour(data)?;
+        let (data, eta_minute_utc) = parse_minsec(data)?;
+        let (data, assisting_tugboats) = map(take_bits(3u8), |tugboats| match tugboats {
+            7 => None,
+            _ => Some(tugboats),
+        })(data)?;
+        let (data, air_draught) = map(take_bits(12u16), |air_draught| match air_draught {
+            0 => None,
+            _ => Some(air_draught as f32 / 100.0),
+        })(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(5u8)(data)?;
+        Ok((
+            data,
+            InlandEta {
+                location,
+                eta_month_utc,
+                eta_day_utc,
+                eta_hour_utc,
+                eta_minute_utc,
+                assisting_tugboats,
+                air_draught,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use crate::messages::addressed_binary_message::{
+        AddressedApplicationData, AddressedBinaryMessage,
+    };
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_inland_eta() {
+        let bitstream = pack_bits(&[
+            (6, 6),
+            (0, 2),
+            (211512340, 30),
+            (0, 2),
+            (2113100, 30),
+            (0, 1),
+            (0, 1),
+            (200, 10),
+            (21, 6),
+            // "DE", "DUI", "00070", "LOCK1", "00123" in 6-bit ASCII
+            (0o0405, 12),
+            (0o042511, 18),
+            (0o6060606760, 30),
+            (0o1417031361, 30),
+            (0o6060616263, 30),
+            (7, 4),
+            (14, 5),
+            (9, 5),
+            (45, 6),
+            (2, 3),
+            (1250, 12),
+            (0, 5),
+        ]);
+        let message = AddressedBinaryMessage::parse(bitstream.as_ref()).unwrap();
+        let eta = match message.application_data().unwrap() {
+            Some(AddressedApplicationData::InlandEta(eta)) => eta,
+            other => panic!("Expected an inland ETA, got {:?}", other),
+        };
+        assert_eq!(eta.location.country, "DE");
+        assert_eq!(eta.location.location, "DUI");
+        assert_eq!(eta.location.fairway_section, "00070");
+        assert_eq!(eta.location.terminal, "LOCK1");
+        assert_eq!(eta.location.fairway_hectometre, "00123");
+        assert_eq!(eta.eta_month_utc, Some(7));
+        assert_eq!(eta.eta_day_utc, Some(14));
+        assert_eq!(eta.eta_hour_utc, 9);
+        assert_eq!(eta.eta_minute_utc, Some(45));
+        assert_eq!(eta.assisting_tugboats, Some(2));
+        assert_eq!(eta.air_draught, Some(12.5));
+    }
+}
@@ -0,0 +1,108 @@
+//! Inland RTA at Lock/Bridge/Terminal (type 6, DAC 200, FI 22)
+use super::bit_reader::BitReader;
+use super::inland_eta::InlandLocation;
+use super::parsers::*;
+use crate::errors::Result;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LockStatus {
+    Operational,
+    LimitedOperation,
+    OutOfOrder,
+}
+
+impl LockStatus {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0 => Some(Self::Operational),
+            1 => Some(Self::LimitedOperation),
+            2 => Some(Self::OutOfOrder),
+            3 => None,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Recommended time of arrival at a lock, bridge or terminal, sent to a vessel
+/// in response to its ETA
+#[derive(Debug, PartialEq, Eq)]
+pub struct InlandRta {
+    pub location: InlandLocation,
+    pub rta_month_utc: Option<u8>,
+    pub rta_day_utc: Option<u8>,
+    pub rta_hour_utc: u8,
+    pub rta_minute_utc: Option<u8>,
+    pub lock_status: Option<LockStatus>,
+}
+
+impl InlandRta {
+    /// Parses the binary payload of a DAC 200, FI 22 addressed binary message
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(data);
+        let location = reader.run(InlandLocation::parse)?;
+        let rta_month_utc = reader.run(parse_month)?;
+        let rta_day_utc = reader.run(parse_day)?;
+        let rta_hour_utc = reader.run(parse_hour)?;
+        let rta_minute_utc = reader.run(parse_minsec)?;
+        let lock_status = LockStatus::parse(reader.read_u32(2)? as u8);
+        reader.skip(2)?;
+        Ok(Self {
+            location,
+            rta_month_utc,
+            rta_day_utc,
+            rta_hour_utc,
+            rta_minute_utc,
+            lock_status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::addressed_binary_message::{
+        AddressedApplicationData, AddressedBinaryMessage,
+    };
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_inland_rta() {
+        let bitstream = pack_bits(&[
+            (6, 6),
+            (0, 2),
+            (2113100, 30),
+            (0, 2),
+            (211512340, 30),
+            (0, 1),
+            (0, 1),
+            (200, 10),
+            (22, 6),
+            // "DE", "DUI", "00070", "LOCK1", "00123" in 6-bit ASCII
+            (0o0405, 12),
+            (0o042511, 18),
+            (0o6060606760, 30),
+            (0o1417031361, 30),
+            (0o6060616263, 30),
+            (7, 4),
+            (14, 5),
+            (10, 5),
+            (60, 6),
+            (1, 2),
+            (0, 2),
+        ]);
+        let message = AddressedBinaryMessage::parse(bitstream.as_ref()).unwrap();
+        let rta = match message.application_data().unwrap() {
+            Some(AddressedApplicationData::InlandRta(rta)) => rta,
+            other => panic!("Expected an inland RTA, got {:?}", other),
+        };
+        assert_eq!(rta.location.country, "DE");
+        assert_eq!(rta.location.terminal, "LOCK1");
+        assert_eq!(rta.rta_month_utc, Some(7));
+        assert_eq!(rta.rta_day_utc, Some(14));
+        assert_eq!(rta.rta_hour_utc, 10);
+        assert_eq!(rta.rta_minute_utc, None);
+        assert_eq!(rta.lock_status, Some(LockStatus::LimitedOperation));
+    }
+}
@@ -0,0 +1,73 @@
+//! A small gazetteer of UN/LOCODE port codes, for making sense of destinations
+//! (requires the `locode` feature)
+
+/// A port, as listed in UN/LOCODE
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PortInfo {
+    /// Five character code: the country, then the location
+    pub locode: &'static str,
+    pub name: &'static str,
+    /// Degrees, to the nearest minute
+    pub latitude: f32,
+    /// Degrees, to the nearest minute
+    pub longitude: f32,
+}
+
+const fn port(locode: &'static str, name: &'static str, latitude: f32, longitude: f32) -> PortInfo {
+    PortInfo {
+        locode,
+        name,
+        latitude,
+        longitude,
+    }
+}
+
+/// Some of the busiest ports. This is far from the full list, which has over
+/// 100,000 locations.
+pub const PORTS: &[PortInfo] = &[
+    port("AEJEA", "Jebel Ali", 25.0, 55.05),
+    port("BEANR", "Antwerp", 51.2167, 4.4167),
+    port("CNSHA", "Shanghai", 31.2333, 121.4833),
+    port("DEBRV", "Bremerhaven", 53.55, 8.5833),
+    port("DEHAM", "Hamburg", 53.55, 9.9833),
+    port("GBFXT", "Felixstowe", 51.9667, 1.35),
+    port("HKHKG", "Hong Kong", 22.3, 114.1667),
+    port("NLAMS", "Amsterdam", 52.3833, 4.9),
+    port("NLRTM", "Rotterdam", 51.9167, 4.5),
+    port("SGSIN", "Singapore", 1.2833, 103.8333),
+    port("USLAX", "Los Angeles", 33.7167, -118.2667),
+    port("USOAK", "Oakland", 37.8, -122.2667),
+];
+
+/// Looks up a five character UN/LOCODE, such as `NLRTM`, ignoring case and any
+/// space between the country and location parts
+pub fn lookup(code: &str) -> Option<PortInfo> {
+    let code = code.trim().as_bytes();
+    let (country, location) = match code.len() {
+        5 => (&code[..2], &code[2..]),
+        6 if code[2] == b' ' => (&code[..2], &code[3..]),
+        _ => return None,
+    };
+    PORTS
+        .iter()
+        .find(|port| {
+            let locode = port.locode.as_bytes();
+            country.eq_ignore_ascii_case(&locode[..2])
+                && location.eq_ignore_ascii_case(&locode[2..])
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_locode() {
+        assert_eq!(lookup("NLRTM").unwrap().name, "Rotterdam");
+        assert_eq!(lookup("nl rtm").unwrap().name, "Rotterdam");
+        assert_eq!(lookup("NLXXX"), None);
+        assert_eq!(lookup("ROTTERDAM"), None);
+        assert!(PORTS.windows(2).all(|pair| pair[0].locode < pair[1].locode));
+    }
+}
@@ -0,0 +1,172 @@
+//! Long Range AIS Broadcast Message (type 27)
+use super::navigation::{Accuracy, Coordinate};
+use super::parsers::*;
+use super::position_report::NavigationStatus;
+use super::AisMessageType;
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// A cut-down position report for reception by satellite, with coarser
+/// position, speed and course than types 1-3
+#[derive(Debug, PartialEq)]
+pub struct LongRangeBroadcastMessage {
+    pub message_type: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    /// As for types 1-3: `Dgps` means the position is accurate to 10m or better.
+    /// The position itself is only sent to the nearest 1/10 minute (about
+    /// 185m), so this describes the fix rather than the reported value.
+    pub position_accuracy: Accuracy,
+    pub raim: bool,
+    pub navigation_status: NavigationStatus,
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
+    /// Knots, to the nearest knot
+    pub speed_over_ground: Option<f32>,
+    /// Degrees, to the nearest degree
+    pub course_over_ground: Option<f32>,
+    /// Whether the position is more than 5 seconds old, or not from a GNSS fix.
+    /// Type 27 has no time stamp field, so this stands in for one.
+    pub position_latency: bool,
+}
+
+impl<'a> AisMessageType<'a> for LongRangeBroadcastMessage {
+    fn name(&self) -> &'static str {
+        "Long Range AIS Broadcast Message"
+    }
+
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        let (_, report) = parse_base(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_longitude_min_10(data: i32) -> Option<f32> {
+    match data {
+        108_600 => None,
+        _ => Some(data as f32 / 600.0),
+    }
+}
+
+fn parse_latitude_min_10(data: i32) -> Option<f32> {
+    match data {
+        54_600 => None,
+        _ => Some(data as f32 / 600.0),
+    }
+}
+
+fn parse_base(data: &[u8]) -> IResult<&[u8], LongRangeBroadcastMessage> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_type) = take_bits(6u8)(data)?;
+        let (data, repeat_indicator) = take_bits(2u8)(data)?;
+        let (data, mmsi) = take_bits(30u32)(data)?;
+        let (data, position_accuracy) = map(take_bits(1u8), Accuracy::parse)(data)?;
+        let (data, raim) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, navigation_status) = map(take_bits(4u8), NavigationStatus::parse)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 18)?;
+        let (data, raw_latitude) = signed_i32(data, 17)?;
+        let (data, speed_over_ground) = map(take_bits(6u8), |speed| match speed {
+            63 => None,
+            _ => Some(speed as f32),
+        })(data)?;
+        let (data, course_over_ground) = map(take_bits(9u16), |course| match course {
+            511 => None,
+            _ => Some(course as f32),
+        })(data)?;
+        let (data, position_latency) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(1u8)(data)?;
+        Ok((
+            data,
+            LongRangeBroadcastMessage {
+                message_type,
+                repeat_indicator,
+                mmsi,
+                position_accuracy,
+                raim,
+                navigation_status,
+                longitude: parse_longitude_min_10(raw_longitude),
+                latitude: parse_latitude_min_10(raw_latitude),
+                coordinate: Coordinate::from_tenth_minutes(raw_latitude, raw_longitude),
+                speed_over_ground,
+                course_over_ground,
+                position_latency,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_type27() {
+        let bitstream = pack_bits(&[
+            (27, 6),
+            (3, 2),
+            (236091959, 30),
+            (1, 1),
+            (0, 1),
+            (5, 4),
+            (-7_380i64 as u64, 18),
+            (21_300, 17),
+            (11, 6),
+            (248, 9),
+            (1, 1),
+            (0, 1),
+        ]);
+        let report = LongRangeBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.message_type, 27);
+        assert_eq!(report.repeat_indicator, 3);
+        assert_eq!(report.mmsi, 236091959);
+        assert_eq!(report.position_accuracy, Accuracy::Dgps);
+        assert!(!report.raim);
+        assert_eq!(report.navigation_status, NavigationStatus::Moored);
+        assert_eq!(report.longitude, Some(-12.3));
+        assert_eq!(report.latitude, Some(35.5));
+        assert_eq!(
+            report.coordinate,
+            Some(Coordinate {
+                latitude: 35.5,
+                longitude: -12.3
+            })
+        );
+        assert_eq!(report.speed_over_ground, Some(11.0));
+        assert_eq!(report.course_over_ground, Some(248.0));
+        assert!(report.position_latency);
+    }
+
+    #[test]
+    fn test_type27_unavailable() {
+        let bitstream = pack_bits(&[
+            (27, 6),
+            (0, 2),
+            (236091959, 30),
+            (0, 1),
+            (1, 1),
+            (15, 4),
+            (108_600, 18),
+            (54_600, 17),
+            (63, 6),
+            (511, 9),
+            (0, 1),
+            (0, 1),
+        ]);
+        let report = LongRangeBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.position_accuracy, Accuracy::Unaugmented);
+        assert!(report.raim);
+        assert_eq!(report.navigation_status, NavigationStatus::NotDefined);
+        assert_eq!(report.longitude, None);
+        assert_eq!(report.latitude, None);
+        assert_eq!(report.coordinate, None);
+        assert_eq!(report.speed_over_ground, None);
+        assert_eq!(report.course_over_ground, None);
+        assert!(!report.position_latency);
+    }
+}
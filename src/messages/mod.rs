@@ -1,28 +1,50 @@
 //! Specific AIS message types
-use crate::errors::Result;
+use crate::errors::{Error, Result};
 use crate::lib;
 use crate::sentence::AisRawData;
 
+pub mod addressed_binary_message;
 pub mod aid_to_navigation_report;
 pub mod base_station_report;
 pub mod binary_broadcast_message;
+mod bit_reader;
 pub mod data_link_management_message;
 pub mod dgnss_broadcast_binary_message;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod encoder;
 pub mod extended_class_b_position_report;
+pub mod extended_ship_static_data;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod field_table;
+#[cfg(all(feature = "geojson", any(feature = "std", feature = "alloc")))]
+mod geojson;
+pub mod inland_eta;
+pub mod inland_rta;
 pub mod interrogation;
+#[cfg(feature = "locode")]
+pub mod locode;
+pub mod long_range_broadcast;
 pub mod navigation;
 #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
 mod nom_noalloc;
 mod parsers;
+pub mod persons_on_board;
 pub mod position_report;
 pub mod radio_status;
+pub mod route_information;
 pub mod standard_class_b_position_report;
 pub mod static_and_voyage_related_data;
 pub mod static_data_report;
 pub mod types;
 pub mod utc_date_response;
+pub mod vts_generated_targets;
 pub mod standard_aircraft_position_report;
 
+pub use bit_reader::BitReader;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use encoder::{armor, encode};
+#[cfg(all(feature = "geojson", any(feature = "std", feature = "alloc")))]
+pub use geojson::geojson_feature_collection;
 pub use parsers::message_type;
 
 #[cfg(feature = "alloc")]
@@ -44,16 +66,282 @@ pub enum AisMessage {
     StaticDataReport(static_data_report::StaticDataReport),
     UtcDateResponse(utc_date_response::UtcDateResponse),
     StandardAircraftPositionReport(standard_aircraft_position_report::SARPositionReport),
+    AddressedBinaryMessage(addressed_binary_message::AddressedBinaryMessage),
+    LongRangeBroadcastMessage(long_range_broadcast::LongRangeBroadcastMessage),
+}
+
+impl AisMessage {
+    /// The MMSI of the station that sent this message
+    pub fn mmsi(&self) -> u32 {
+        match self {
+            Self::PositionReport(report) => report.mmsi,
+            Self::BaseStationReport(report) => report.mmsi,
+            Self::BinaryBroadcastMessage(message) => message.mmsi,
+            Self::Interrogation(message) => message.mmsi,
+            Self::StaticAndVoyageRelatedData(report) => report.mmsi,
+            Self::DgnssBroadcastBinaryMessage(message) => message.mmsi,
+            Self::StandardClassBPositionReport(report) => report.mmsi,
+            Self::ExtendedClassBPositionReport(report) => report.mmsi,
+            Self::DataLinkManagementMessage(message) => message.mmsi,
+            Self::AidToNavigationReport(report) => report.mmsi,
+            Self::StaticDataReport(report) => report.mmsi,
+            Self::UtcDateResponse(report) => report.mmsi,
+            Self::StandardAircraftPositionReport(report) => report.mmsi,
+            Self::AddressedBinaryMessage(message) => message.mmsi,
+            Self::LongRangeBroadcastMessage(report) => report.mmsi,
+        }
+    }
+
+    /// The message type number, as transmitted
+    pub fn message_type(&self) -> u8 {
+        match self {
+            Self::PositionReport(report) => report.message_type,
+            Self::BaseStationReport(report) => report.message_type,
+            Self::BinaryBroadcastMessage(message) => message.message_type,
+            Self::Interrogation(message) => message.message_type,
+            Self::StaticAndVoyageRelatedData(report) => report.message_type,
+            Self::DgnssBroadcastBinaryMessage(message) => message.message_type,
+            Self::StandardClassBPositionReport(report) => report.message_type,
+            Self::ExtendedClassBPositionReport(report) => report.message_type,
+            Self::DataLinkManagementMessage(message) => message.message_type,
+            Self::AidToNavigationReport(report) => report.message_type,
+            Self::StaticDataReport(report) => report.message_type,
+            Self::UtcDateResponse(report) => report.message_type,
+            Self::StandardAircraftPositionReport(report) => report.message_type,
+            Self::AddressedBinaryMessage(message) => message.message_type,
+            Self::LongRangeBroadcastMessage(report) => report.message_type,
+        }
+    }
+
+    /// A number identifying the variant, for use as a column value or partition
+    /// key. Unlike the message type, this is the same for types 1-3 and both
+    /// parts of type 24. Numbers are never reused, and new variants get the next
+    /// unused one.
+    pub fn discriminant(&self) -> u8 {
+        match self {
+            Self::PositionReport(_) => 0,
+            Self::BaseStationReport(_) => 1,
+            Self::BinaryBroadcastMessage(_) => 2,
+            Self::Interrogation(_) => 3,
+            Self::StaticAndVoyageRelatedData(_) => 4,
+            Self::DgnssBroadcastBinaryMessage(_) => 5,
+            Self::StandardClassBPositionReport(_) => 6,
+            Self::ExtendedClassBPositionReport(_) => 7,
+            Self::DataLinkManagementMessage(_) => 8,
+            Self::AidToNavigationReport(_) => 9,
+            Self::StaticDataReport(_) => 10,
+            Self::UtcDateResponse(_) => 11,
+            Self::StandardAircraftPositionReport(_) => 12,
+            Self::AddressedBinaryMessage(_) => 13,
+            Self::LongRangeBroadcastMessage(_) => 14,
+        }
+    }
+
+    /// The common position fields of any kind of position report (types 1-3, 9,
+    /// 18, 19 and 27), or `None` for other messages
+    pub fn as_position(&self) -> Option<navigation::PositionView> {
+        match self {
+            Self::PositionReport(report) => Some(navigation::PositionView {
+                mmsi: report.mmsi,
+                latitude: report.latitude,
+                longitude: report.longitude,
+                coordinate: report.coordinate,
+                speed_over_ground: report.speed_over_ground,
+                course_over_ground: report.course_over_ground,
+                true_heading: report.true_heading,
+                timestamp: report.timestamp,
+            }),
+            Self::StandardClassBPositionReport(report) => Some(navigation::PositionView {
+                mmsi: report.mmsi,
+                latitude: report.latitude,
+                longitude: report.longitude,
+                coordinate: report.coordinate,
+                speed_over_ground: report.speed_over_ground,
+                course_over_ground: report.course_over_ground,
+                true_heading: report.true_heading,
+                timestamp: report.timestamp,
+            }),
+            Self::ExtendedClassBPositionReport(report) => Some(navigation::PositionView {
+                mmsi: report.mmsi,
+                latitude: report.latitude,
+                longitude: report.longitude,
+                coordinate: report.coordinate,
+                speed_over_ground: report.speed_over_ground,
+                course_over_ground: report.course_over_ground,
+                true_heading: report.true_heading,
+                timestamp: report.timestamp,
+            }),
+            Self::StandardAircraftPositionReport(report) => Some(navigation::PositionView {
+                mmsi: report.mmsi,
+                latitude: report.latitude,
+                longitude: report.longitude,
+                coordinate: report.coordinate,
+                speed_over_ground: report.speed_over_ground,
+                course_over_ground: report.course_over_ground,
+                true_heading: None,
+                timestamp: report.timestamp,
+            }),
+            // Type 27 has no heading or time stamp
+            Self::LongRangeBroadcastMessage(report) => Some(navigation::PositionView {
+                mmsi: report.mmsi,
+                latitude: report.latitude,
+                longitude: report.longitude,
+                coordinate: report.coordinate,
+                speed_over_ground: report.speed_over_ground,
+                course_over_ground: report.course_over_ground,
+                true_heading: None,
+                timestamp: 60,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether the sender is reporting autonomously or as assigned by a type 16 or
+    /// 23 command, for the message types which carry the flag (9, 18, 19 and 21)
+    pub fn assigned_mode(&self) -> Option<types::AssignedMode> {
+        match self {
+            Self::StandardAircraftPositionReport(report) => Some(report.assigned_mode),
+            Self::StandardClassBPositionReport(report) => Some(report.assigned_mode),
+            Self::ExtendedClassBPositionReport(report) => Some(report.assigned_mode),
+            Self::AidToNavigationReport(report) => {
+                Some(types::AssignedMode::parse(report.assigned_mode as u8))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether this is a retransmission of an addressed message, for the message
+    /// types which carry the flag. Only type 6 is decoded so far; type 12 will
+    /// join it once it is supported.
+    pub fn retransmit(&self) -> Option<bool> {
+        match self {
+            Self::AddressedBinaryMessage(message) => Some(message.retransmit),
+            _ => None,
+        }
+    }
+
+    /// The class of vessel station that sent this message, if the message type is
+    /// specific to one. Base stations, aids to navigation, and so on return `None`.
+    pub fn station_class(&self) -> Option<types::StationClass> {
+        match self {
+            Self::PositionReport(_) | Self::StaticAndVoyageRelatedData(_) => {
+                Some(types::StationClass::ClassA)
+            }
+            Self::StandardClassBPositionReport(_)
+            | Self::ExtendedClassBPositionReport(_)
+            | Self::StaticDataReport(_) => Some(types::StationClass::ClassB),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn vessel_name(&self) -> Option<&str> {
+        let name = match self {
+            Self::StaticAndVoyageRelatedData(report) => &report.vessel_name,
+            Self::ExtendedClassBPositionReport(report) => &report.name,
+            Self::StaticDataReport(static_data_report::StaticDataReport {
+                message_part: static_data_report::MessagePart::PartA { vessel_name },
+                ..
+            }) => vessel_name,
+            _ => return None,
+        };
+        Some(name.as_str()).filter(|name| !name.is_empty())
+    }
+
+    pub(crate) fn callsign(&self) -> Option<&str> {
+        let callsign = match self {
+            Self::StaticAndVoyageRelatedData(report) => &report.callsign,
+            Self::StaticDataReport(static_data_report::StaticDataReport {
+                message_part: static_data_report::MessagePart::PartB { callsign, .. },
+                ..
+            }) => callsign,
+            _ => return None,
+        };
+        Some(callsign.as_str()).filter(|callsign| !callsign.is_empty())
+    }
+
+    /// The type of ship and cargo, for the message types which carry it (5, 19
+    /// and part B of 24)
+    pub fn ship_type(&self) -> Option<types::ShipType> {
+        match self {
+            Self::StaticAndVoyageRelatedData(report) => report.ship_type,
+            Self::ExtendedClassBPositionReport(report) => report.type_of_ship_and_cargo,
+            Self::StaticDataReport(static_data_report::StaticDataReport {
+                message_part: static_data_report::MessagePart::PartB { ship_type, .. },
+                ..
+            }) => *ship_type,
+            _ => None,
+        }
+    }
+
+    pub(crate) fn dimensions(&self) -> Option<(u16, u16, u16, u16)> {
+        let dimensions = match self {
+            Self::StaticAndVoyageRelatedData(report) => (
+                report.dimension_to_bow,
+                report.dimension_to_stern,
+                report.dimension_to_port,
+                report.dimension_to_starboard,
+            ),
+            Self::ExtendedClassBPositionReport(report) => (
+                report.dimension_to_bow,
+                report.dimension_to_stern,
+                report.dimension_to_port,
+                report.dimension_to_starboard,
+            ),
+            Self::StaticDataReport(static_data_report::StaticDataReport {
+                message_part:
+                    static_data_report::MessagePart::PartB {
+                        dimension_to_bow,
+                        dimension_to_stern,
+                        dimension_to_port,
+                        dimension_to_starboard,
+                        ..
+                    },
+                ..
+            }) => (
+                *dimension_to_bow,
+                *dimension_to_stern,
+                *dimension_to_port,
+                *dimension_to_starboard,
+            ),
+            _ => return None,
+        };
+        // All zeroes means the dimensions aren't known
+        Some(dimensions).filter(|dimensions| *dimensions != (0, 0, 0, 0))
+    }
 }
 
 /// Trait that describes specific types of AIS messages
 pub trait AisMessageType<'a>: Sized {
     /// The common name for the message type
     fn name(&self) -> &'static str;
-    /// Converts a raw AIS message into a structured, queryable version
+    /// Converts a raw AIS message into a structured, queryable version.
+    ///
+    /// Any bits beyond the end of the message, such as extra spare padding
+    /// added by some equipment, are ignored.
     fn parse(data: &'a [u8]) -> Result<Self>;
 }
 
+/// Returns whether two messages probably came from the same vessel, such as one
+/// which has switched between class A and class B transponders.
+///
+/// Messages with the same MMSI always match. Otherwise, the vessel name or
+/// callsign has to be the same in both, and none of the name, callsign and
+/// dimensions may differ where both messages carry them. Only static data
+/// (types 5, 19 and 24) can match across MMSIs.
+pub fn likely_same_vessel(a: &AisMessage, b: &AisMessage) -> bool {
+    if a.mmsi() == b.mmsi() {
+        return true;
+    }
+    fn compare<T: PartialEq>(a: Option<T>, b: Option<T>) -> Option<bool> {
+        Some(a? == b?)
+    }
+    let name = compare(a.vessel_name(), b.vessel_name());
+    let callsign = compare(a.callsign(), b.callsign());
+    let dimensions = compare(a.dimensions(), b.dimensions());
+    let identified = name == Some(true) || callsign == Some(true);
+    identified && ![name, callsign, dimensions].contains(&Some(false))
+}
+
 /// Given an unarmored bitstream (see [`unarmor()`](fn.unarmor.html) for details), this
 /// will return a message type object, if supported by this library
 /// and the message is valid.
@@ -70,6 +358,9 @@ pub fn parse(unarmored: &[u8]) -> Result<AisMessage> {
         5 => Ok(AisMessage::StaticAndVoyageRelatedData(
             static_and_voyage_related_data::StaticAndVoyageRelatedData::parse(unarmored)?,
         )),
+        6 => Ok(AisMessage::AddressedBinaryMessage(
+            addressed_binary_message::AddressedBinaryMessage::parse(unarmored)?,
+        )),
         8 => Ok(AisMessage::BinaryBroadcastMessage(
             binary_broadcast_message::BinaryBroadcastMessage::parse(unarmored)?,
         )),
@@ -100,10 +391,59 @@ pub fn parse(unarmored: &[u8]) -> Result<AisMessage> {
         24 => Ok(AisMessage::StaticDataReport(
             static_data_report::StaticDataReport::parse(unarmored)?,
         )),
-        #[cfg(any(feature = "std", feature = "alloc"))]
-        _ => Err(format!("Unimplemented type: {}", result).into()),
-        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-        _ => Err("Unimplemented message type".into()),
+        27 => Ok(AisMessage::LongRangeBroadcastMessage(
+            long_range_broadcast::LongRangeBroadcastMessage::parse(unarmored)?,
+        )),
+        _ => Err(Error::UnsupportedMessageType(result)),
+    }
+}
+
+/// How a message's length compares with what ITU-R M.1371 allows for its type;
+/// see [`validate_length()`](fn.validate_length.html)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LengthCheck {
+    Valid,
+    /// Shorter than the minimum for the type, which suggests truncation
+    Short,
+    /// Longer than the maximum for the type, which suggests extra padding
+    Long,
+    /// The message type isn't defined by the standard
+    UnknownType,
+}
+
+/// Checks a message's length in bits, not counting fill bits, against the range
+/// allowed for its type. Types with a variable length, such as binary messages,
+/// accept anything from their shortest to their longest form.
+pub fn validate_length(message_type: u8, bit_length: usize) -> LengthCheck {
+    let (min, max) = match message_type {
+        1..=4 | 9 | 11 | 18 | 22 => (168, 168),
+        5 => (424, 424),
+        6 => (88, 1008),
+        7 | 13 => (72, 168),
+        8 => (56, 1008),
+        10 => (72, 72),
+        12 => (72, 1008),
+        14 => (40, 1008),
+        15 => (88, 160),
+        16 => (96, 144),
+        17 => (80, 816),
+        19 => (312, 312),
+        20 => (72, 160),
+        21 => (272, 360),
+        23 => (160, 160),
+        // Part A is 160 bits, and part B 168
+        24 => (160, 168),
+        25 => (40, 168),
+        26 => (60, 1064),
+        27 => (96, 96),
+        _ => return LengthCheck::UnknownType,
+    };
+    if bit_length < min {
+        LengthCheck::Short
+    } else if bit_length > max {
+        LengthCheck::Long
+    } else {
+        LengthCheck::Valid
     }
 }
 
@@ -118,12 +458,98 @@ pub fn parse(unarmored: &[u8]) -> Result<AisMessage> {
 /// as part of the main sentence.
 ///
 /// Returns an error if any of the individual bytes cannot be converted
-/// to a valid 6-bit chunk.
+/// to a valid 6-bit chunk, or if `fill_bits` is not in the range 0-5 allowed
+/// for an armored payload of this length.
 ///
 /// See <https://gpsd.gitlab.io/gpsd/AIVDM.html> for more details.
 pub fn unarmor(data: &[u8], fill_bits: usize) -> Result<AisRawData> {
+    unarmor_bytes(data, fill_bits, |byte| match byte {
+        48..=87 => Some(byte - 48),
+        96..=119 => Some(byte - 56),
+        _ => None,
+    })
+}
+
+/// Like [`unarmor()`](fn.unarmor.html), but for payloads armored with a
+/// nonstandard alphabet
+pub fn unarmor_with_alphabet(
+    data: &[u8],
+    fill_bits: usize,
+    alphabet: &SixBitAlphabet,
+) -> Result<AisRawData> {
+    unarmor_bytes(data, fill_bits, |byte| alphabet.value(byte))
+}
+
+/// The characters used to armor each 6-bit value, for decoding the few
+/// proprietary feeds which don't use the standard ones; see
+/// [`unarmor_with_alphabet()`](fn.unarmor_with_alphabet.html)
+///
+/// # Example:
+/// ```
+/// use ais::messages::{unarmor_with_alphabet, SixBitAlphabet};
+///
+/// // The standard alphabet, shifted along by one character
+/// let mut chars = *SixBitAlphabet::STANDARD_CHARS;
+/// for char in chars.iter_mut() {
+///     *char += 1;
+/// }
+/// let alphabet = SixBitAlphabet::new(&chars)?;
+/// assert_eq!(unarmor_with_alphabet(b"2", 0, &alphabet)?[..], [1 << 2]);
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SixBitAlphabet {
+    /// The value of each character, or `INVALID`
+    values: [u8; 256],
+}
+
+impl SixBitAlphabet {
+    /// The characters for each value in the standard alphabet
+    pub const STANDARD_CHARS: &'static [u8; 64] =
+        b"0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVW`abcdefghijklmnopqrstuvw";
+
+    const INVALID: u8 = 0xff;
+
+    /// Creates an alphabet where `chars[n]` is the character for the value `n`.
+    /// The characters have to be distinct.
+    pub fn new(chars: &[u8; 64]) -> Result<Self> {
+        let mut values = [Self::INVALID; 256];
+        for (value, &char) in chars.iter().enumerate() {
+            if values[char as usize] != Self::INVALID {
+                return Err("Character appears more than once in the alphabet".into());
+            }
+            values[char as usize] = value as u8;
+        }
+        Ok(Self { values })
+    }
+
+    /// The 6-bit value armored as `char`, if it is part of the alphabet
+    pub fn value(&self, char: u8) -> Option<u8> {
+        match self.values[char as usize] {
+            Self::INVALID => None,
+            value => Some(value),
+        }
+    }
+}
+
+impl Default for SixBitAlphabet {
+    fn default() -> Self {
+        Self::new(Self::STANDARD_CHARS).expect("the standard characters are distinct")
+    }
+}
+
+fn unarmor_bytes<F>(data: &[u8], fill_bits: usize, value: F) -> Result<AisRawData>
+where
+    F: Fn(u8) -> Option<u8>,
+{
     let bit_count = data.len() * 6;
-    let byte_count = (bit_count / 8) + ((bit_count % 8 != 0) as usize);
+    if fill_bits > 5 || fill_bits > bit_count {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        return Err(format!("Invalid fill bit count: {}", fill_bits).into());
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        return Err("Invalid fill bit count".into());
+    }
+    let byte_count = (bit_count / 8) + (!bit_count.is_multiple_of(8) as usize);
     #[cfg(any(feature = "std", feature = "alloc"))]
     let mut output = vec![0; byte_count];
     #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
@@ -136,13 +562,12 @@ pub fn unarmor(data: &[u8], fill_bits: usize) -> Result<AisRawData> {
     };
     let mut offset = 0;
     for byte in data {
-        let unarmored = match *byte {
-            48..=87 => byte - 48,
-            96..=119 => byte - 56,
+        let unarmored = match value(*byte) {
+            Some(value) => value,
             #[cfg(any(feature = "std", feature = "alloc"))]
-            _ => return Err(format!("Value out of range: {}", byte).into()),
+            None => return Err(format!("Value out of range: {}", byte).into()),
             #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-            _ => return Err("Armored byte value out of range".into()),
+            None => return Err("Armored byte value out of range".into()),
         } << 2;
         let offset_byte = offset / 8;
         let offset_bit = offset % 8;
@@ -257,5 +682,196 @@ mod tests {
         let result = unarmor(input, 3).unwrap();
         assert_eq!([0b0010_0111, 0b1001_1000, 0b0000_0000], &result[..]);
     }
+
+    #[test]
+    fn unarmor_empty() {
+        let result = unarmor(b"", 0).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn unarmor_custom_alphabet() {
+        let standard = SixBitAlphabet::default();
+        assert_eq!(standard.value(b'0'), Some(0));
+        assert_eq!(standard.value(b'w'), Some(63));
+        assert_eq!(standard.value(b'X'), None);
+
+        // Shifted down by 8 characters
+        let mut chars = *SixBitAlphabet::STANDARD_CHARS;
+        for char in chars.iter_mut() {
+            *char -= 8;
+        }
+        let alphabet = SixBitAlphabet::new(&chars).unwrap();
+        let payload = b"13u?etPv2;0n:dDPwUM1U1Cb069D";
+        let shifted = payload.map(|char| chars[standard.value(char).unwrap() as usize]);
+        assert_eq!(
+            unarmor_with_alphabet(&shifted, 0, &alphabet).unwrap(),
+            unarmor(payload, 0).unwrap()
+        );
+        assert!(unarmor_with_alphabet(b"w", 0, &alphabet).is_err());
+
+        let mut duplicated = *SixBitAlphabet::STANDARD_CHARS;
+        duplicated[1] = b'0';
+        assert!(SixBitAlphabet::new(&duplicated).is_err());
+    }
+
+    #[test]
+    fn unarmor_invalid_fill_bits() {
+        // These used to panic, indexing before the start of the output buffer
+        assert!(unarmor(b"", 2).is_err());
+        assert!(unarmor(b"9", 6).is_err());
+        assert!(unarmor(b"9", 20).is_err());
+    }
+
+    #[test]
+    fn station_class() {
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        assert_eq!(
+            parse(&bitstream).unwrap().station_class(),
+            Some(types::StationClass::ClassA)
+        );
+        let bitstream = unarmor(b"B6:hQDh0029Pt<4TAS003h6TSP00", 0).unwrap();
+        assert_eq!(
+            parse(&bitstream).unwrap().station_class(),
+            Some(types::StationClass::ClassB)
+        );
+        let bitstream = unarmor(b"403OtVAv6s5l1o?I``E`4I?02<34", 0).unwrap();
+        assert_eq!(parse(&bitstream).unwrap().station_class(), None);
+    }
+
+    #[test]
+    fn as_position() {
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let position = parse(&bitstream).unwrap().as_position().unwrap();
+        assert_eq!(position.mmsi, 265547250);
+        assert_eq!(position.speed_over_ground, Some(13.9));
+        assert_eq!(position.course_over_ground, Some(40.4));
+        assert_eq!(position.true_heading, Some(41));
+        assert_eq!(position.timestamp, 53);
+        assert!(position.latitude.is_some() && position.longitude.is_some());
+
+        let bitstream = unarmor(b"B6:hQDh0029Pt<4TAS003h6TSP00", 0).unwrap();
+        let position = parse(&bitstream).unwrap().as_position().unwrap();
+        assert_eq!(position.mmsi, 413933907);
+        assert_eq!(position.speed_over_ground, Some(0.0));
+
+        let bitstream = unarmor(b"403OtVAv6s5l1o?I``E`4I?02<34", 0).unwrap();
+        assert_eq!(parse(&bitstream).unwrap().as_position(), None);
+    }
+
+    #[test]
+    fn unsupported_types() {
+        let bitstream = crate::test_helpers::pack_bits(&[(7, 6), (0, 2), (3669145, 30)]);
+        assert_eq!(parse(&bitstream), Err(Error::UnsupportedMessageType(7)));
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        assert!(parse(&bitstream).is_ok());
+    }
+
+    #[test]
+    fn validate_type1_length() {
+        assert_eq!(validate_length(1, 168), LengthCheck::Valid);
+        assert_eq!(validate_length(1, 162), LengthCheck::Short);
+        assert_eq!(validate_length(1, 192), LengthCheck::Long);
+        assert_eq!(validate_length(8, 400), LengthCheck::Valid);
+        assert_eq!(validate_length(24, 160), LengthCheck::Valid);
+        assert_eq!(validate_length(0, 168), LengthCheck::UnknownType);
+    }
+
+    #[test]
+    fn mmsi_and_message_type() {
+        let bitstream = unarmor(b"33nQ:B50000FiEBRjpcK19qSR>`<", 0).unwrap();
+        let message = parse(&bitstream).unwrap();
+        assert_eq!(message.mmsi(), 258493000);
+        assert_eq!(message.message_type(), 3);
+    }
+
+    #[test]
+    fn discriminants() {
+        let discriminant =
+            |payload: &[u8]| parse(&unarmor(payload, 0).unwrap()).unwrap().discriminant();
+        assert_eq!(discriminant(b"13u?etPv2;0n:dDPwUM1U1Cb069D"), 0);
+        assert_eq!(discriminant(b"33nQ:B50000FiEBRjpcK19qSR>`<"), 0);
+        assert_eq!(discriminant(b"403OtVAv6s5l1o?I``E`4I?02<34"), 1);
+        assert_eq!(discriminant(b"B6:hQDh0029Pt<4TAS003h6TSP00"), 6);
+        assert_eq!(discriminant(b"H6:lEgQL4r1<QDr0P4pN3KSKP00"), 10);
+        assert_eq!(discriminant(b"H3mr@L4NC=D62?P<7nmpl00@8220"), 10);
+    }
+
+    #[test]
+    fn assigned_mode() {
+        let bitstream = crate::test_helpers::pack_bits(&[
+            (18, 6),
+            (0, 2),
+            (338087471, 30),
+            (0, 8),
+            (62, 10),
+            (0, 1),
+            (-44_290_000i64 as u64, 28),
+            (24_420_000, 27),
+            (1830, 12),
+            (511, 9),
+            (21, 6),
+            (0, 2),
+            (1, 1),
+            (0, 1),
+            (1, 1),
+            (1, 1),
+            (1, 1),
+            (1, 1),
+            (0, 1),
+            (1, 1),
+            (0, 19),
+        ]);
+        let message = parse(&bitstream).unwrap();
+        assert_eq!(message.assigned_mode(), Some(types::AssignedMode::Assigned));
+
+        let bitstream = unarmor(b"B6:hQDh0029Pt<4TAS003h6TSP00", 0).unwrap();
+        let message = parse(&bitstream).unwrap();
+        assert_eq!(
+            message.assigned_mode(),
+            Some(types::AssignedMode::Autonomous)
+        );
+
+        // Class A position reports have no assigned mode flag
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        assert_eq!(parse(&bitstream).unwrap().assigned_mode(), None);
+    }
+
+    #[test]
+    fn same_vessel_across_mmsis() {
+        let bitstream = unarmor(b"H6:lEgQL4r1<QDr0P4pN3KSKP00", 0).unwrap();
+        let class_b = parse(&bitstream).unwrap();
+        let renamed = |mmsi, vessel_name: &str| {
+            AisMessage::StaticDataReport(static_data_report::StaticDataReport {
+                message_type: 24,
+                repeat_indicator: 0,
+                mmsi,
+                message_part: static_data_report::MessagePart::PartA {
+                    vessel_name: vessel_name.into(),
+                },
+            })
+        };
+        assert!(likely_same_vessel(
+            &class_b,
+            &renamed(413996000, "WAN SHUN HANG 6868")
+        ));
+        assert!(!likely_same_vessel(
+            &class_b,
+            &renamed(413996000, "WAN SHUN HANG 6869")
+        ));
+        assert!(likely_same_vessel(&class_b, &renamed(413996478, "")));
+
+        // Nothing to compare
+        let bitstream = unarmor(b"H3mr@L4NC=D62?P<7nmpl00@8220", 0).unwrap();
+        let part_b = parse(&bitstream).unwrap();
+        assert!(!likely_same_vessel(&class_b, &part_b));
+    }
+
+    #[test]
+    fn parse_arbitrary_bytes() {
+        for data in [&b""[..], b"\x00", b"\xff\xff\xff", b"\x04\x00\x00\x00\x00"] {
+            assert!(parse(data).is_err());
+        }
+    }
     // TODO: test parse i32
 }
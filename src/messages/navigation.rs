@@ -1,3 +1,5 @@
+use super::position_report::PositionReport;
+
 pub fn parse_speed_over_ground(data: u16) -> Option<f32> {
     match data {
         1023 => None,
@@ -5,17 +7,50 @@ pub fn parse_speed_over_ground(data: u16) -> Option<f32> {
     }
 }
 
+/// Converts a longitude in 1/10000 minutes. 181 degrees means not available,
+/// and anything else outside ±180 degrees is treated the same way.
 pub fn parse_longitude(data: i32) -> Option<f32> {
     match data {
-        108_600_000 => None,
-        _ => Some(data as f32 / 600_000.0),
+        -108_000_000..=108_000_000 => Some(data as f32 / 600_000.0),
+        _ => None,
     }
 }
 
+/// Converts a latitude in 1/10000 minutes. 91 degrees means not available, and
+/// anything else outside ±90 degrees is treated the same way.
 pub fn parse_latitude(data: i32) -> Option<f32> {
     match data {
-        54_600_000 => None,
-        _ => Some(data as f32 / 600_000.0),
+        -54_000_000..=54_000_000 => Some(data as f32 / 600_000.0),
+        _ => None,
+    }
+}
+
+/// A position in degrees, with positive latitudes north and positive longitudes
+/// east
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Coordinate {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinate {
+    /// Converts a latitude and longitude in 1/10000 minutes, as sent in position
+    /// reports, keeping the full precision. Returns `None` if either is out of
+    /// range, which includes the 91° and 181° not available values.
+    pub fn new(latitude: i32, longitude: i32) -> Option<Self> {
+        // The same range checks as the `f32` fields, without their rounding
+        parse_latitude(latitude)?;
+        parse_longitude(longitude)?;
+        Some(Self {
+            latitude: latitude as f64 / 600_000.0,
+            longitude: longitude as f64 / 600_000.0,
+        })
+    }
+
+    /// Like [`new()`](#method.new), for the 1/10 minutes of long range broadcasts
+    pub(crate) fn from_tenth_minutes(latitude: i32, longitude: i32) -> Option<Self> {
+        // At most 18 bits, so this can't overflow
+        Self::new(latitude * 1000, longitude * 1000)
     }
 }
 
@@ -33,6 +68,24 @@ pub fn parse_heading(data: u16) -> Option<u16> {
     }
 }
 
+/// The fields shared by all position reports, whatever the message type; see
+/// [`AisMessage::as_position()`](../enum.AisMessage.html#method.as_position)
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PositionView {
+    pub mmsi: u32,
+    pub latitude: Option<f32>,
+    pub longitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
+    pub speed_over_ground: Option<f32>,
+    pub course_over_ground: Option<f32>,
+    /// Always `None` for SAR aircraft, which don't report a heading
+    pub true_heading: Option<u16>,
+    /// Seconds of UTC time stamp; see [`Timestamp`](enum.Timestamp.html). Long
+    /// range broadcasts don't carry one, so they report 60, for not available.
+    pub timestamp: u8,
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Accuracy {
     Unaugmented,
@@ -49,6 +102,11 @@ impl Accuracy {
     }
 }
 
+/// Rate of turn, as reported by class A position reports. The field is `None`
+/// in the report when no turn information is available.
+///
+/// A turn faster than 5° per 30s with no rate of turn indicator fitted only
+/// has a direction, and no [`rate()`](#method.rate).
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct RateOfTurn {
     raw: i8,
@@ -69,15 +127,31 @@ impl RateOfTurn {
         }
     }
 
+    /// The rate of turn in degrees per minute, which is negative for turns to port.
+    /// The reported value is the square root of the rate, scaled by 4.733, so this
+    /// undoes that. Rates of 708°/min and over are reported as 708.
+    ///
+    /// `None` for turns faster than 5° per 30s whose rate isn't known.
     pub fn rate(self) -> Option<f32> {
         match self.raw {
-            -126..=126 => Some((self.raw as f32 / 4.733) * (self.raw as f32 / 4.733)),
+            -126..=126 => {
+                let magnitude = (self.raw as f32 / 4.733) * (self.raw as f32 / 4.733);
+                // Squaring loses the sign, so put it back
+                Some(if self.raw < 0 { -magnitude } else { magnitude })
+            }
             -127 => None,
             127 => None,
             _ => unreachable!(),
         }
     }
 
+    /// The value as transmitted
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) fn raw(self) -> i8 {
+        self.raw
+    }
+
+    /// Which way the vessel is turning, or `None` if it isn't
     pub fn direction(self) -> Option<Direction> {
         match self.raw {
             0 => None,
@@ -104,3 +178,154 @@ impl ManeuverIndicator {
         }
     }
 }
+
+/// Meaning of the seconds of UTC time stamp field in position reports
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Timestamp {
+    /// Second of the UTC minute when the report was generated
+    Second(u8),
+    NotAvailable,
+    /// Positioning system is in manual input mode
+    ManualInput,
+    /// Positioning system is in estimated (dead reckoning) mode
+    Estimated,
+    /// Positioning system is inoperative
+    Inoperative,
+}
+
+impl Timestamp {
+    pub fn parse(data: u8) -> Self {
+        match data {
+            0..=59 => Self::Second(data),
+            61 => Self::ManualInput,
+            62 => Self::Estimated,
+            63 => Self::Inoperative,
+            _ => Self::NotAvailable,
+        }
+    }
+
+    /// The second of the UTC minute, if one was given
+    pub fn second(self) -> Option<u8> {
+        match self {
+            Self::Second(second) => Some(second),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the initial great circle bearing in degrees, from 0 up to 360, actually
+/// travelled between two consecutive position reports. This can differ from the
+/// reported course over ground, which makes it useful for validating tracks.
+///
+/// Returns `None` if either report is missing a position, or if the position
+/// did not change.
+#[cfg(feature = "std")]
+pub fn course_made_good(prev: &PositionReport, next: &PositionReport) -> Option<f64> {
+    let lat1 = (prev.latitude? as f64).to_radians();
+    let lon1 = (prev.longitude? as f64).to_radians();
+    let lat2 = (next.latitude? as f64).to_radians();
+    let lon2 = (next.longitude? as f64).to_radians();
+    if lat1 == lat2 && lon1 == lon2 {
+        return None;
+    }
+    let delta_lon = lon2 - lon1;
+    let y = delta_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos();
+    Some(y.atan2(x).to_degrees().rem_euclid(360.0))
+}
+
+/// Returns whether the shortest path between two consecutive position reports
+/// crosses the antimeridian, at 180° longitude, so that track renderers know to
+/// split the line there. Tracks are assumed never to go more than halfway round
+/// the world between reports.
+///
+/// Returns `false` if either report is missing a valid position.
+pub fn crossed_antimeridian(prev: &PositionReport, next: &PositionReport) -> bool {
+    match (prev.coordinate, next.coordinate) {
+        (Some(prev), Some(next)) => (next.longitude - prev.longitude).abs() > 180.0,
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::messages::AisMessageType;
+
+    fn report_at(latitude: Option<f32>, longitude: Option<f32>) -> PositionReport {
+        let bitstream = crate::messages::unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let mut report = PositionReport::parse(bitstream.as_ref()).unwrap();
+        report.latitude = latitude;
+        report.longitude = longitude;
+        report.coordinate = latitude
+            .zip(longitude)
+            .map(|(latitude, longitude)| Coordinate {
+                latitude: latitude as f64,
+                longitude: longitude as f64,
+            });
+        report
+    }
+
+    #[test]
+    fn test_rate_of_turn() {
+        // -48 as a two's complement byte: turning to port
+        let port = RateOfTurn::parse(0xd0).unwrap();
+        assert_eq!(port.direction(), Some(Direction::Port));
+        let rate = port.rate().unwrap();
+        assert!((rate + 102.85).abs() < 0.01, "rate was {}", rate);
+        let starboard = RateOfTurn::parse(0x30).unwrap();
+        assert_eq!(starboard.direction(), Some(Direction::Starboard));
+        assert_eq!(starboard.rate(), Some(-rate));
+        assert_eq!(RateOfTurn::parse(0).unwrap().rate(), Some(0.0));
+        assert_eq!(RateOfTurn::parse(0).unwrap().direction(), None);
+        // No turn information available
+        assert_eq!(RateOfTurn::parse(0x80), None);
+        // Turning at more than 5° per 30s, with no indicator
+        let fast = RateOfTurn::parse(0x7f).unwrap();
+        assert_eq!(fast.rate(), None);
+        assert_eq!(fast.direction(), Some(Direction::Starboard));
+        let fast = RateOfTurn::parse(0x81).unwrap();
+        assert_eq!(fast.rate(), None);
+        assert_eq!(fast.direction(), Some(Direction::Port));
+        // The largest rate that can be reported
+        let rate = RateOfTurn::parse(0x7e).unwrap().rate().unwrap();
+        assert!((rate - 708.0).abs() < 1.0, "rate was {}", rate);
+    }
+
+    #[test]
+    fn test_course_made_good() {
+        let prev = report_at(Some(37.8), Some(-122.4));
+        let next = report_at(Some(37.7), Some(-122.3));
+        let course = course_made_good(&prev, &next).unwrap();
+        assert!((course - 141.636).abs() < 0.001, "course was {}", course);
+        let north = report_at(Some(37.9), Some(-122.4));
+        assert_eq!(course_made_good(&prev, &north), Some(0.0));
+    }
+
+    #[test]
+    fn test_crossed_antimeridian() {
+        let east = report_at(Some(-17.5), Some(179.0));
+        let west = report_at(Some(-17.5), Some(-179.0));
+        assert!(crossed_antimeridian(&east, &west));
+        assert!(crossed_antimeridian(&west, &east));
+        assert!(!crossed_antimeridian(&east, &east));
+        // Crossing the prime meridian instead
+        assert!(!crossed_antimeridian(
+            &report_at(Some(51.5), Some(-0.5)),
+            &report_at(Some(51.5), Some(0.5))
+        ));
+        assert!(!crossed_antimeridian(&east, &report_at(None, Some(-179.0))));
+        assert!(!crossed_antimeridian(&report_at(Some(-17.5), None), &west));
+    }
+
+    #[test]
+    fn test_course_made_good_without_movement_or_position() {
+        let prev = report_at(Some(37.8), Some(-122.4));
+        assert_eq!(course_made_good(&prev, &prev), None);
+        assert_eq!(
+            course_made_good(&prev, &report_at(None, Some(-122.4))),
+            None
+        );
+        assert_eq!(course_made_good(&report_at(Some(37.8), None), &prev), None);
+    }
+}
@@ -1,19 +1,18 @@
 //! Common parsers
-use crate::errors::Result;
 use crate::lib;
 
 #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
 use super::nom_noalloc::count;
 use lib::std::string::String;
 use nom::bits::{bits, complete::take as take_bits};
-use nom::combinator::{map, map_res};
+use nom::combinator::map;
 use nom::error::ErrorKind;
 #[cfg(any(feature = "std", feature = "alloc"))]
 use nom::multi::count;
 use nom::IResult;
 
 #[cfg(feature = "alloc")]
-use crate::lib::std::{format, string::ToString};
+use crate::lib::std::string::ToString;
 
 /// This is the maximum number of bytes that a 6-bit ASCII representation can turn into.
 /// The largest we see anywhere is 120 bits = 20 ASCII bytes.
@@ -69,10 +68,10 @@ pub fn parse_6bit_ascii(
 ) -> IResult<(&[u8], usize), AsciiString> {
     let char_count = size / 6;
     #[cfg(any(feature = "std", feature = "alloc"))]
-    let (input, bytes) = count(map_res(take_bits(6u8), sixbit_to_ascii), char_count)(input)?;
+    let (input, bytes) = count(map(take_bits(6u8), sixbit_to_ascii), char_count)(input)?;
     #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
     let (input, bytes) = count::<_, _, _, _, MAX_6BIT_ARRAY_BYTES>(
-        map_res(take_bits(6u8), sixbit_to_ascii),
+        map(take_bits(6u8), sixbit_to_ascii),
         char_count,
     )(input)?;
     #[cfg(any(feature = "std", feature = "alloc"))]
@@ -114,20 +113,13 @@ pub fn message_type_bits(data: (&[u8], usize)) -> IResult<(&[u8], usize), u8> {
     take_bits(6u8)(data)
 }
 
+/// Maps a 6-bit character code to ASCII. Every one of the 64 codes has an
+/// equivalent, so this can't fail.
 #[inline]
-fn sixbit_to_ascii(data: u8) -> Result<u8> {
-    #[cfg(any(feature = "std", feature = "alloc"))]
-    match data {
-        0..=31 => Ok(data + 64),
-        32..=63 => Ok(data),
-        _ => Err(format!("Illegal 6-bit character: {}", data).into()),
-    }
-
-    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+fn sixbit_to_ascii(data: u8) -> u8 {
     match data {
-        0..=31 => Ok(data + 64),
-        32..=63 => Ok(data),
-        _ => Err("Illegal 6-bit character".into()),
+        0..=31 => data + 64,
+        _ => data,
     }
 }
 
@@ -154,3 +146,19 @@ pub fn signed_i32(input: (&[u8], usize), len: usize) -> IResult<(&[u8], usize),
         },
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn parse_6bit_ascii_symbols() {
+        // "N^\_" uses the rarely seen symbol codes, which still come out as
+        // plain ASCII
+        let bitstream = pack_bits(&[(14, 6), (30, 6), (28, 6), (31, 6), (0, 6)]);
+        let (_, name) = parse_6bit_ascii((bitstream.as_ref(), 0), 30).unwrap();
+        assert_eq!(name, "N^\\_");
+        assert!(name.bytes().all(|byte| byte.is_ascii_graphic()));
+    }
+}
@@ -0,0 +1,57 @@
+//! Number of Persons on Board, broadcast (type 8, DAC 1, FI 40)
+use super::bit_reader::BitReader;
+use crate::errors::Result;
+
+/// A count of everyone on board, including crew, as used by search and rescue
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct PersonsOnBoard {
+    /// `None` if not available. 8191 means 8191 or more.
+    pub persons: Option<u16>,
+}
+
+impl PersonsOnBoard {
+    /// Parses the binary payload of a DAC 1, FI 40 binary broadcast message
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(data);
+        let persons = match reader.read_u32(13)? {
+            0 => None,
+            persons => Some(persons as u16),
+        };
+        // The remaining 3 bits are spare
+        Ok(Self { persons })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary_broadcast_message::{
+        BinaryBroadcastMessage, BroadcastApplicationData,
+    };
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_persons_on_board() {
+        let bitstream = pack_bits(&[
+            (8, 6),
+            (0, 2),
+            (366999712, 30),
+            (0, 2),
+            (1, 10),
+            (40, 6),
+            (312, 13),
+            (0, 3),
+        ]);
+        let message = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(
+            message.application_data().unwrap(),
+            Some(BroadcastApplicationData::PersonsOnBoard(PersonsOnBoard {
+                persons: Some(312)
+            }))
+        );
+        assert_eq!(PersonsOnBoard::parse(&[0, 0]).unwrap().persons, None);
+        assert!(PersonsOnBoard::parse(&[0x10]).is_err());
+    }
+}
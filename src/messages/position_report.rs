@@ -13,12 +13,19 @@ pub struct PositionReport {
     pub message_type: u8,
     pub repeat_indicator: u8,
     pub mmsi: u32,
-    pub navigation_status: Option<NavigationStatus>,
+    pub navigation_status: NavigationStatus,
     pub rate_of_turn: Option<RateOfTurn>,
     pub speed_over_ground: Option<f32>,
     pub position_accuracy: Accuracy,
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available. The
+    /// unavailable marker values of 181° and 91° have already been turned into
+    /// `None` in `longitude` and `latitude`, so this also saves checking both.
+    ///
+    /// When encoding, this is written in place of `longitude` and `latitude` if
+    /// it is set, so a position changed in one needs changing in the other too.
+    pub coordinate: Option<Coordinate>,
     pub course_over_ground: Option<f32>,
     pub true_heading: Option<u16>,
     pub timestamp: u8,
@@ -27,6 +34,13 @@ pub struct PositionReport {
     pub radio_status: RadioStatus,
 }
 
+impl PositionReport {
+    /// Interprets the `timestamp` field, which can also carry the state of the positioning system
+    pub fn timestamp_status(&self) -> Timestamp {
+        Timestamp::parse(self.timestamp)
+    }
+}
+
 impl<'a> AisMessageType<'a> for PositionReport {
     fn name(&self) -> &'static str {
         "Position Report Class A"
@@ -47,8 +61,8 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], PositionReport> {
         let (data, rate_of_turn) = map(take_bits(8u8), RateOfTurn::parse)(data)?;
         let (data, speed_over_ground) = map(take_bits(10u16), parse_speed_over_ground)(data)?;
         let (data, position_accuracy) = map(take_bits(1u8), Accuracy::parse)(data)?;
-        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
-        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 28)?;
+        let (data, raw_latitude) = signed_i32(data, 27)?;
         let (data, course_over_ground) = map(take_bits(12u16), parse_cog)(data)?;
         let (data, true_heading) = map(take_bits(9u16), parse_heading)(data)?;
         let (data, timestamp) = take_bits(6u8)(data)?;
@@ -66,8 +80,9 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], PositionReport> {
                 rate_of_turn,
                 speed_over_ground,
                 position_accuracy,
-                longitude,
-                latitude,
+                longitude: parse_longitude(raw_longitude),
+                latitude: parse_latitude(raw_latitude),
+                coordinate: Coordinate::new(raw_latitude, raw_longitude),
                 course_over_ground,
                 true_heading,
                 timestamp,
@@ -92,37 +107,82 @@ pub enum NavigationStatus {
     UnderWaySailing,
     ReservedForHSC,
     ReservedForWIG,
-    Reserved01,
-    Reserved02,
-    Reserved03,
     AisSartIsActive,
-    Unknown(u8),
+    /// Status 15, the default when no status has been set
+    NotDefined,
+    /// Statuses 11 to 13, reserved for future use, with the raw value
+    Reserved(u8),
 }
 
 impl NavigationStatus {
-    fn parse(data: u8) -> Option<Self> {
+    pub(crate) fn parse(data: u8) -> Self {
         match data {
-            0 => Some(Self::UnderWayUsingEngine),
-            1 => Some(Self::AtAnchor),
-            2 => Some(Self::NotUnderCommand),
-            3 => Some(Self::RestrictedManouverability),
-            4 => Some(Self::ConstrainedByDraught),
-            5 => Some(Self::Moored),
-            6 => Some(Self::Aground),
-            7 => Some(Self::EngagedInFishing),
-            8 => Some(Self::UnderWaySailing),
-            9 => Some(Self::ReservedForHSC),
-            10 => Some(Self::ReservedForWIG),
-            11 => Some(Self::Reserved01),
-            12 => Some(Self::Reserved02),
-            13 => Some(Self::Reserved03),
-            14 => Some(Self::AisSartIsActive),
-            15 => None,
-            _ => Some(Self::Unknown(data)),
+            0 => Self::UnderWayUsingEngine,
+            1 => Self::AtAnchor,
+            2 => Self::NotUnderCommand,
+            3 => Self::RestrictedManouverability,
+            4 => Self::ConstrainedByDraught,
+            5 => Self::Moored,
+            6 => Self::Aground,
+            7 => Self::EngagedInFishing,
+            8 => Self::UnderWaySailing,
+            9 => Self::ReservedForHSC,
+            10 => Self::ReservedForWIG,
+            14 => Self::AisSartIsActive,
+            15 => Self::NotDefined,
+            _ => Self::Reserved(data),
+        }
+    }
+
+    /// A suggested RGB color for drawing vessels with this status on a chart:
+    /// green when under way, red when not under command or aground, amber when
+    /// restricted in some way, blue when stationary and magenta for an active
+    /// AIS-SART. Reserved and undefined statuses are grey.
+    pub fn suggested_color(&self) -> (u8, u8, u8) {
+        match self {
+            Self::UnderWayUsingEngine | Self::UnderWaySailing => (0, 170, 0),
+            Self::NotUnderCommand | Self::Aground => (220, 0, 0),
+            Self::RestrictedManouverability
+            | Self::ConstrainedByDraught
+            | Self::EngagedInFishing => (255, 165, 0),
+            Self::AtAnchor | Self::Moored => (0, 90, 200),
+            Self::AisSartIsActive => (220, 0, 220),
+            Self::ReservedForHSC | Self::ReservedForWIG | Self::NotDefined | Self::Reserved(_) => {
+                (128, 128, 128)
+            }
         }
     }
 }
 
+/// Display labels for each navigation status code, indexed by the raw 4-bit value
+pub const NAVIGATION_STATUS_LABELS: [&str; 16] = [
+    "Under way using engine",
+    "At anchor",
+    "Not under command",
+    "Restricted manoeuvrability",
+    "Constrained by her draught",
+    "Moored",
+    "Aground",
+    "Engaged in fishing",
+    "Under way sailing",
+    "Reserved for high speed craft (HSC)",
+    "Reserved for wing in ground (WIG)",
+    "Power-driven vessel towing astern",
+    "Power-driven vessel pushing ahead or towing alongside",
+    "Reserved for future use",
+    "AIS-SART active",
+    "Not defined",
+];
+
+/// The display label for a raw navigation status code. Codes which don't fit in
+/// 4 bits are labelled `"Unknown"`.
+pub fn navigation_status_label(code: u8) -> &'static str {
+    NAVIGATION_STATUS_LABELS
+        .get(code as usize)
+        .copied()
+        .unwrap_or("Unknown")
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unreadable_literal)]
@@ -140,10 +200,10 @@ mod tests {
         assert_eq!(position.mmsi, 265547250);
         assert_eq!(
             position.navigation_status,
-            Some(NavigationStatus::UnderWayUsingEngine)
+            NavigationStatus::UnderWayUsingEngine
         );
         let rate_of_turn = position.rate_of_turn.unwrap();
-        f32_equal_naive(rate_of_turn.rate().unwrap().ceil(), 3.0);
+        f32_equal_naive(rate_of_turn.rate().unwrap().floor(), -3.0);
         assert_eq!(rate_of_turn.direction(), Some(Direction::Port));
         assert_eq!(position.speed_over_ground, Some(13.9));
         assert_eq!(position.position_accuracy, Accuracy::Unaugmented);
@@ -168,6 +228,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_coordinate() {
+        let fields = |longitude: i32, latitude: i32| {
+            pack_bits(&[
+                (1, 6),
+                (0, 2),
+                (265547250, 30),
+                (0, 4),
+                (128, 8),
+                (0, 10),
+                (0, 1),
+                (longitude as u64, 28),
+                (latitude as u64, 27),
+                (3600, 12),
+                (511, 9),
+                (60, 6),
+                (0, 6),
+                (0, 19),
+            ])
+        };
+        let report = PositionReport::parse(fields(-73_998_000, 24_460_800).as_ref()).unwrap();
+        let coordinate = report.coordinate.unwrap();
+        assert!((coordinate.latitude - 40.768).abs() < 1e-5);
+        assert!((coordinate.longitude + 123.33).abs() < 1e-5);
+        // Near 180°, an f32 can only get within a few 1/10000 minutes
+        let report = PositionReport::parse(fields(107_999_999, 0).as_ref()).unwrap();
+        assert_ne!(report.longitude.unwrap() as f64 * 600_000.0, 107_999_999.0);
+        assert_eq!(
+            report.coordinate.unwrap().longitude * 600_000.0,
+            107_999_999.0
+        );
+
+        // 181 and 91 degrees mean not available
+        let report = PositionReport::parse(fields(108_600_000, 54_600_000).as_ref()).unwrap();
+        assert_eq!(report.longitude, None);
+        assert_eq!(report.latitude, None);
+        assert_eq!(report.coordinate, None);
+        let report = PositionReport::parse(fields(-73_998_000, 54_600_000).as_ref()).unwrap();
+        assert_eq!(report.coordinate, None);
+        // As are other values out of range
+        let report = PositionReport::parse(fields(120_000_000, 24_460_800).as_ref()).unwrap();
+        assert_eq!(report.longitude, None);
+        assert_eq!(report.coordinate, None);
+    }
+
+    #[test]
+    fn test_navigation_status() {
+        assert_eq!(NavigationStatus::parse(1), NavigationStatus::AtAnchor);
+        assert_eq!(NavigationStatus::parse(12), NavigationStatus::Reserved(12));
+        assert_eq!(NavigationStatus::parse(15), NavigationStatus::NotDefined);
+    }
+
+    #[test]
+    fn suggested_colors() {
+        assert_eq!(
+            NavigationStatus::UnderWayUsingEngine.suggested_color(),
+            (0, 170, 0)
+        );
+        assert_eq!(
+            NavigationStatus::NotUnderCommand.suggested_color(),
+            (220, 0, 0)
+        );
+        assert_eq!(
+            NavigationStatus::Reserved(11).suggested_color(),
+            NavigationStatus::NotDefined.suggested_color()
+        );
+    }
+
+    #[test]
+    fn test_type1_over_padded() {
+        // Same as test_position, with 24 extra spare bits on the end
+        let bytestream = b"13u?etPv2;0n:dDPwUM1U1Cb069D0000";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let position = PositionReport::parse(bitstream.as_ref()).unwrap();
+        let bitstream = crate::messages::unarmor(&bytestream[..28], 0).unwrap();
+        assert_eq!(position, PositionReport::parse(bitstream.as_ref()).unwrap());
+    }
+
     #[test]
     fn test_type1() {
         let bytestream = b"16SteH0P00Jt63hHaa6SagvJ087r";
@@ -190,10 +328,7 @@ mod tests {
         let position = PositionReport::parse(bitstream.as_ref()).unwrap();
         assert_eq!(position.message_type, 3);
         assert_eq!(position.mmsi, 563808000);
-        assert_eq!(
-            position.navigation_status.unwrap(),
-            NavigationStatus::Moored
-        );
+        assert_eq!(position.navigation_status, NavigationStatus::Moored);
         f32_equal_naive(position.longitude.unwrap(), -76.32753);
         f32_equal_naive(position.latitude.unwrap(), 36.91);
         f32_equal_naive(position.course_over_ground.unwrap(), 252.0);
@@ -209,6 +344,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_type3_raim_and_itdma() {
+        let bytestream = b"33nQ:B50000FiEBRjpcK19qSR>`<";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let position = PositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(position.message_type, 3);
+        assert_eq!(position.mmsi, 258493000);
+        assert!(position.raim);
+        // Type 3 carries ITDMA communication state, unlike types 1 and 2
+        if let RadioStatus::Itdma(radio_status) = position.radio_status {
+            assert_eq!(radio_status.sync_state, SyncState::UtcDirect);
+            assert_eq!(radio_status.slot_increment, 3744);
+            assert_eq!(radio_status.num_slots, 6);
+            assert!(!radio_status.keep);
+        } else {
+            panic!("Expected ITDMA message");
+        }
+    }
+
+    #[test]
+    fn test_timestamp_status() {
+        let bytestream = b"13u?etPv2;0n:dDPwUM1U1Cb069D";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let mut position = PositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(position.timestamp_status(), Timestamp::Second(53));
+        assert_eq!(position.timestamp_status().second(), Some(53));
+        position.timestamp = 60;
+        assert_eq!(position.timestamp_status(), Timestamp::NotAvailable);
+        assert_eq!(position.timestamp_status().second(), None);
+        position.timestamp = 63;
+        assert_eq!(position.timestamp_status(), Timestamp::Inoperative);
+    }
+
     #[test]
     fn test_maneuver_indicator_out_of_spec() {
         let bytestream = b"33nQ:B50000FiEBRjpcK19qSR>`<";
@@ -219,4 +387,14 @@ mod tests {
             Some(ManeuverIndicator::Unknown(3))
         );
     }
+
+    #[test]
+    fn test_navigation_status_label() {
+        assert_eq!(navigation_status_label(0), "Under way using engine");
+        assert_eq!(navigation_status_label(1), "At anchor");
+        assert_eq!(navigation_status_label(5), "Moored");
+        assert_eq!(navigation_status_label(13), "Reserved for future use");
+        assert_eq!(navigation_status_label(15), "Not defined");
+        assert_eq!(navigation_status_label(16), "Unknown");
+    }
 }
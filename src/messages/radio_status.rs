@@ -1,4 +1,5 @@
 use super::parsers::u8_to_bool;
+use core::ops::RangeInclusive;
 use nom::bits::complete::take as take_bits;
 use nom::combinator::map;
 use nom::error::ErrorKind;
@@ -10,6 +11,58 @@ pub enum RadioStatus {
     Itdma(ItdmaMessage),
 }
 
+/// Number of TDMA slots in each one minute frame
+pub const SLOTS_PER_FRAME: u16 = 2250;
+
+impl RadioStatus {
+    pub fn sync_state(&self) -> SyncState {
+        match self {
+            Self::Sotdma(sotdma) => sotdma.sync_state,
+            Self::Itdma(itdma) => itdma.sync_state,
+        }
+    }
+
+    /// Number of other stations the sender can hear, if the communication state
+    /// carries it. Only SOTDMA messages with a slot timeout of 3, 5 or 7 do.
+    pub fn received_stations(&self) -> Option<u16> {
+        match self {
+            Self::Sotdma(sotdma) => sotdma.received_stations(),
+            Self::Itdma(_) => None,
+        }
+    }
+
+    /// Estimates which slots of the one minute TDMA frame the message was sent in,
+    /// given the second of the UTC minute it was sent, such as the position
+    /// report time stamp.
+    ///
+    /// SOTDMA messages sometimes carry the exact slot number, in which case that
+    /// is used. Otherwise the range covers the 37.5 slots in that second. Stations
+    /// which aren't synchronized to UTC, directly, indirectly or through a base
+    /// station, can't be placed in the frame, and return `None`, as do seconds
+    /// outside 0-59.
+    pub fn estimated_slots(&self, utc_second: u8) -> Option<RangeInclusive<u16>> {
+        if let Self::Sotdma(SotdmaMessage {
+            sub_message: SubMessage::SlotNumber(slot),
+            ..
+        }) = self
+        {
+            if *slot < SLOTS_PER_FRAME {
+                return Some(*slot..=*slot);
+            }
+        }
+        match self.sync_state() {
+            SyncState::UtcDirect | SyncState::UtcIndirect | SyncState::BaseStation => {}
+            _ => return None,
+        }
+        if utc_second >= 60 {
+            return None;
+        }
+        // 37.5 slots per second, so a slot can straddle two seconds
+        let second = utc_second as u16;
+        Some(second * 75 / 2..=((second + 1) * 75).div_ceil(2) - 1)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum SyncState {
     UtcDirect,
@@ -96,6 +149,15 @@ impl SotdmaMessage {
             }),
         ))
     }
+
+    /// Number of other stations the sender can hear, sent in place of the slot
+    /// number when the slot timeout is 3, 5 or 7
+    pub fn received_stations(&self) -> Option<u16> {
+        match self.sub_message {
+            SubMessage::ReceivedStations(stations) => Some(stations),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -134,3 +196,162 @@ pub fn parse_radio(input: (&[u8], usize), msg_type: u8) -> IResult<(&[u8], usize
         ))),
     }
 }
+
+/// Estimates how busy a channel is from the communication states of the
+/// messages received on it over some period, for monitoring the RF environment.
+///
+/// Each message is taken to occupy the slot it was sent in, or for ITDMA, the
+/// number of consecutive slots it announces, so the estimate only covers what
+/// was actually received.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChannelLoad {
+    messages: u32,
+    slots: u32,
+    slot_timeouts: [u32; 8],
+    received_stations: Option<u16>,
+}
+
+impl ChannelLoad {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the communication state of one received message
+    pub fn add(&mut self, status: &RadioStatus) {
+        self.messages += 1;
+        match status {
+            RadioStatus::Sotdma(sotdma) => {
+                self.slots += 1;
+                if let Some(count) = self.slot_timeouts.get_mut(sotdma.slot_timeout as usize) {
+                    *count += 1;
+                }
+                self.received_stations = self.received_stations.max(sotdma.received_stations());
+            }
+            RadioStatus::Itdma(itdma) => {
+                // 5-7 mean 1-3 slots, with the slot increment offset by 8192
+                self.slots += match itdma.num_slots {
+                    slots @ 0..=4 => slots as u32 + 1,
+                    slots => slots as u32 - 4,
+                };
+            }
+        }
+    }
+
+    /// Number of messages recorded
+    pub fn messages(&self) -> u32 {
+        self.messages
+    }
+
+    /// Number of SOTDMA messages seen with each slot timeout, from 0 to 7. Many
+    /// at 0 means stations are often moving to new slots.
+    pub fn slot_timeouts(&self) -> &[u32; 8] {
+        &self.slot_timeouts
+    }
+
+    /// The most stations any sender reported receiving, if any sent the count
+    pub fn received_stations(&self) -> Option<u16> {
+        self.received_stations
+    }
+
+    /// The fraction of the channel's slots which were used, from 0 to 1, given
+    /// how many seconds the messages were collected over. There are 37.5 slots
+    /// in each second.
+    pub fn utilization(&self, seconds: u32) -> Option<f32> {
+        if seconds == 0 {
+            return None;
+        }
+        let available = seconds as f32 * SLOTS_PER_FRAME as f32 / 60.0;
+        Some((self.slots as f32 / available).min(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::pack_bits;
+
+    fn sotdma(sync_state: SyncState, sub_message: SubMessage) -> RadioStatus {
+        RadioStatus::Sotdma(SotdmaMessage {
+            sync_state,
+            slot_timeout: 1,
+            sub_message,
+        })
+    }
+
+    #[test]
+    fn estimated_slots_utc_direct() {
+        let status = sotdma(SyncState::UtcDirect, SubMessage::UtcHourAndMinute(17, 21));
+        assert_eq!(status.estimated_slots(0), Some(0..=37));
+        assert_eq!(status.estimated_slots(1), Some(37..=74));
+        assert_eq!(status.estimated_slots(53), Some(1987..=2024));
+        assert_eq!(status.estimated_slots(59), Some(2212..=2249));
+        assert_eq!(status.estimated_slots(60), None);
+    }
+
+    #[test]
+    fn estimated_slots_from_slot_number() {
+        let status = sotdma(
+            SyncState::NumberOfReceivedStations,
+            SubMessage::SlotNumber(1200),
+        );
+        assert_eq!(status.estimated_slots(7), Some(1200..=1200));
+        let status = sotdma(
+            SyncState::NumberOfReceivedStations,
+            SubMessage::SlotOffset(0),
+        );
+        assert_eq!(status.estimated_slots(7), None);
+    }
+
+    #[test]
+    fn parse_received_stations() {
+        // UTC indirect, slot timeout 3, 1234 stations
+        let data = pack_bits(&[(1, 2), (3, 3), (1234, 14)]);
+        let (_, status) = SotdmaMessage::parse((data.as_ref(), 0)).unwrap();
+        assert_eq!(status.sync_state(), SyncState::UtcIndirect);
+        assert_eq!(status.received_stations(), Some(1234));
+        // The same value with slot timeout 2 is a slot number
+        let data = pack_bits(&[(1, 2), (2, 3), (1234, 14)]);
+        let (_, status) = SotdmaMessage::parse((data.as_ref(), 0)).unwrap();
+        assert_eq!(status.received_stations(), None);
+        let itdma = RadioStatus::Itdma(ItdmaMessage {
+            sync_state: SyncState::UtcDirect,
+            slot_increment: 0,
+            num_slots: 0,
+            keep: false,
+        });
+        assert_eq!(itdma.received_stations(), None);
+    }
+
+    #[test]
+    fn channel_load() {
+        let mut load = ChannelLoad::new();
+        assert_eq!(load.utilization(60), Some(0.0));
+        for timeout in 0..8 {
+            load.add(&RadioStatus::Sotdma(SotdmaMessage {
+                sync_state: SyncState::UtcDirect,
+                slot_timeout: timeout,
+                sub_message: match timeout {
+                    3 => SubMessage::ReceivedStations(42),
+                    5 => SubMessage::ReceivedStations(17),
+                    _ => SubMessage::SlotOffset(0),
+                },
+            }));
+        }
+        for num_slots in [0, 4, 6] {
+            load.add(&RadioStatus::Itdma(ItdmaMessage {
+                sync_state: SyncState::UtcDirect,
+                slot_increment: 0,
+                num_slots,
+                keep: false,
+            }));
+        }
+        assert_eq!(load.messages(), 11);
+        assert_eq!(load.slot_timeouts(), &[1; 8]);
+        assert_eq!(load.received_stations(), Some(42));
+        // 8 + 1 + 5 + 2 slots out of 37.5 in a second
+        let utilization = load.utilization(1).unwrap();
+        assert!((utilization - 16.0 / 37.5).abs() < 1e-6);
+        assert!(load.utilization(60).unwrap() < utilization);
+        assert_eq!(load.utilization(0), None);
+    }
+}
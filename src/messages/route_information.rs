@@ -0,0 +1,221 @@
+//! Route Information, broadcast (type 8, DAC 1, FI 27)
+use super::navigation::{parse_latitude, parse_longitude};
+use super::parsers::*;
+use super::push_unwrap;
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Most waypoints a single route can carry
+const MAX_WAYPOINTS: usize = 16;
+/// Each waypoint takes up this many bits of the payload
+const WAYPOINT_SIZE_BITS: usize = 55;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type WaypointList = lib::std::vec::Vec<Waypoint>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type WaypointList = lib::std::vec::Vec<Waypoint, MAX_WAYPOINTS>;
+
+/// A planned or recommended route, as a series of waypoints
+#[derive(Debug, PartialEq)]
+pub struct RouteInformation {
+    /// Ties this route to related messages
+    pub message_linkage_id: u16,
+    pub sender_classification: SenderClassification,
+    pub route_type: RouteType,
+    pub start_month_utc: Option<u8>,
+    pub start_day_utc: Option<u8>,
+    pub start_hour_utc: Option<u8>,
+    pub start_minute_utc: Option<u8>,
+    /// Minutes the route is valid for, from the start time. Zero cancels the route.
+    pub duration: Option<u32>,
+    /// Up to 16 waypoints, in order
+    pub waypoints: WaypointList,
+}
+
+impl RouteInformation {
+    /// Parses the binary payload of a DAC 1, FI 27 binary broadcast message
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, route) = parse_route(data)?;
+        Ok(route)
+    }
+}
+
+/// Who is sending the route
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SenderClassification {
+    Ship,
+    Authority,
+    Reserved(u8),
+}
+
+impl SenderClassification {
+    fn parse(data: u8) -> Self {
+        match data {
+            0 => Self::Ship,
+            1 => Self::Authority,
+            _ => Self::Reserved(data),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum RouteType {
+    Undefined,
+    Mandatory,
+    Recommended,
+    Alternative,
+    RecommendedThroughIce,
+    ShipRoutePlan,
+    /// Cancels the route with the same message linkage ID
+    Cancellation,
+    Reserved(u8),
+}
+
+impl RouteType {
+    fn parse(data: u8) -> Self {
+        match data {
+            0 => Self::Undefined,
+            1 => Self::Mandatory,
+            2 => Self::Recommended,
+            3 => Self::Alternative,
+            4 => Self::RecommendedThroughIce,
+            5 => Self::ShipRoutePlan,
+            31 => Self::Cancellation,
+            _ => Self::Reserved(data),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Waypoint {
+    pub latitude: Option<f32>,
+    pub longitude: Option<f32>,
+}
+
+impl Waypoint {
+    fn parse(data: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
+        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        Ok((
+            data,
+            Self {
+                latitude,
+                longitude,
+            },
+        ))
+    }
+}
+
+fn parse_route(data: &[u8]) -> IResult<&[u8], RouteInformation> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_linkage_id) = take_bits(10u16)(data)?;
+        let (data, sender_classification) = map(take_bits(3u8), SenderClassification::parse)(data)?;
+        let (data, route_type) = map(take_bits(5u8), RouteType::parse)(data)?;
+        let (data, start_month_utc) = map(take_bits(4u8), |month| match month {
+            0 => None,
+            _ => Some(month),
+        })(data)?;
+        let (data, start_day_utc) = map(take_bits(5u8), |day| match day {
+            0 => None,
+            _ => Some(day),
+        })(data)?;
+        let (data, start_hour_utc) = map(take_bits(5u8), |hour| match hour {
+            24 => None,
+            _ => Some(hour),
+        })(data)?;
+        let (data, start_minute_utc) = map(take_bits(6u8), |minute| match minute {
+            60 => None,
+            _ => Some(minute),
+        })(data)?;
+        let (data, duration) = map(take_bits(18u32), |duration| match duration {
+            262_143 => None,
+            _ => Some(duration),
+        })(data)?;
+        let (mut data, waypoint_count) = take_bits::<_, usize, _, _>(5u8)(data)?;
+        let mut waypoints = WaypointList::default();
+        // Trust the payload length over the count, in case of truncation
+        while waypoints.len() < waypoint_count.min(MAX_WAYPOINTS)
+            && remaining_bits(data) >= WAYPOINT_SIZE_BITS
+        {
+            let (remaining, waypoint) = Waypoint::parse(data)?;
+            push_unwrap(&mut waypoints, waypoint);
+            data = remaining;
+        }
+        Ok((
+            (<&[u8]>::default(), 0),
+            RouteInformation {
+                message_linkage_id,
+                sender_classification,
+                route_type,
+                start_month_utc,
+                start_day_utc,
+                start_hour_utc,
+                start_minute_utc,
+                duration,
+                waypoints,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary_broadcast_message::{
+        BinaryBroadcastMessage, BroadcastApplicationData,
+    };
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_route_information() {
+        let bitstream = pack_bits(&[
+            (8, 6),
+            (0, 2),
+            (2442000, 30),
+            (0, 2),
+            (1, 10),
+            (27, 6),
+            (17, 10),
+            (1, 3),
+            (2, 5),
+            (6, 4),
+            (14, 5),
+            (9, 5),
+            (30, 6),
+            (1440, 18),
+            (2, 5),
+            (2_340_000, 28),
+            (31_200_000, 27),
+            (-246_000i64 as u64, 28),
+            (30_600_000, 27),
+        ]);
+        let message = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        let route = match message.application_data().unwrap() {
+            Some(BroadcastApplicationData::RouteInformation(route)) => route,
+            other => panic!("Expected route information, got {:?}", other),
+        };
+        assert_eq!(route.message_linkage_id, 17);
+        assert_eq!(route.sender_classification, SenderClassification::Authority);
+        assert_eq!(route.route_type, RouteType::Recommended);
+        assert_eq!(route.start_month_utc, Some(6));
+        assert_eq!(route.start_day_utc, Some(14));
+        assert_eq!(route.start_hour_utc, Some(9));
+        assert_eq!(route.start_minute_utc, Some(30));
+        assert_eq!(route.duration, Some(1440));
+        assert_eq!(route.waypoints.len(), 2);
+        assert_eq!(
+            route.waypoints[0],
+            Waypoint {
+                latitude: Some(52.0),
+                longitude: Some(3.9),
+            }
+        );
+        assert_eq!(route.waypoints[1].latitude, Some(51.0));
+        assert_eq!(route.waypoints[1].longitude, Some(-0.41));
+    }
+}
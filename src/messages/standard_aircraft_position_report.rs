@@ -22,14 +22,24 @@ pub struct SARPositionReport {
     pub position_accuracy: Accuracy,
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
     pub course_over_ground: Option<f32>,
     pub timestamp: u8,
+    pub altitude_sensor: AltitudeSensor,
     pub dte: Dte,
     pub assigned_mode: AssignedMode,
     pub raim: bool,
     pub radio_status: RadioStatus,
 }
 
+impl SARPositionReport {
+    /// Interprets the `timestamp` field, which can also carry the state of the positioning system
+    pub fn timestamp_status(&self) -> Timestamp {
+        Timestamp::parse(self.timestamp)
+    }
+}
+
 
 impl<'a> AisMessageType<'a> for SARPositionReport {
     fn name(&self) -> &'static str {
@@ -51,11 +61,12 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], SARPositionReport> {
         let (data, altitude) = map(take_bits(12u16), parse_altitude)(data)?;
         let (data, speed_over_ground) = map(take_bits(10u16), parse_speed_over_ground_sar)(data)?;
         let (data, position_accuracy) = map(take_bits(1u8), Accuracy::parse)(data)?;
-        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
-        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 28)?;
+        let (data, raw_latitude) = signed_i32(data, 27)?;
         let (data, course_over_ground) = map(take_bits(12u16), parse_cog)(data)?;
         let (data, timestamp) = take_bits(6u8)(data)?;
-        let (data, _regional_reserved) = take_bits::<_, u8, _, _>(8u8)(data)?;
+        let (data, altitude_sensor) = map(take_bits(1u8), AltitudeSensor::parse)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(7u8)(data)?;
         let (data, dte) = map(take_bits::<_, u8, _, _>(1u8), Into::into)(data)?;
         let (data, _spare) = take_bits::<_, u8, _, _>(3u8)(data)?;
         let (data, assigned_mode) = map(take_bits(1u8), AssignedMode::parse)(data)?;
@@ -71,10 +82,12 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], SARPositionReport> {
                 altitude,
                 speed_over_ground,
                 position_accuracy,
-                longitude,
-                latitude,
+                longitude: parse_longitude(raw_longitude),
+                latitude: parse_latitude(raw_latitude),
+                coordinate: Coordinate::new(raw_latitude, raw_longitude),
                 course_over_ground,
                 timestamp,
+                altitude_sensor,
                 dte,
                 assigned_mode,
                 raim,
@@ -84,6 +97,22 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], SARPositionReport> {
     })(data)
 }
 
+/// The source of the altitude field
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum AltitudeSensor {
+    Gnss,
+    Barometric,
+}
+
+impl AltitudeSensor {
+    pub fn parse(data: u8) -> Self {
+        match data {
+            0 => Self::Gnss,
+            _ => Self::Barometric,
+        }
+    }
+}
+
 /// Parse the altitude field
 fn parse_altitude(data: u16) -> Option<u16> {
     match data {
@@ -125,6 +154,7 @@ mod tests {
         f32_equal_naive(report.latitude.unwrap(), 58.144);
         assert_eq!(report.course_over_ground, Some(154.5));
         assert_eq!(report.timestamp, 15);
+        assert_eq!(report.altitude_sensor, AltitudeSensor::Gnss);
         assert_eq!(report.dte, Dte::NotReady);
         if let RadioStatus::Sotdma(radio_status) = report.radio_status {
             assert_eq!(radio_status.sync_state, SyncState::UtcDirect);
@@ -140,4 +170,34 @@ mod tests {
         }     
         assert!(!report.raim);
     }
+
+    #[test]
+    fn test_type9_flags() {
+        let bitstream = pack_bits(&[
+            (9, 6),
+            (0, 2),
+            (111232511, 30),
+            (1500, 12),
+            (120, 10),
+            (1, 1),
+            (0, 28),
+            (0, 27),
+            (900, 12),
+            (30, 6),
+            (1, 1),
+            (0, 7),
+            (0, 1),
+            (0, 3),
+            (1, 1),
+            (0, 1),
+            (0, 20),
+        ]);
+        let report = SARPositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.altitude, Some(1500));
+        assert_eq!(report.timestamp, 30);
+        assert_eq!(report.altitude_sensor, AltitudeSensor::Barometric);
+        assert_eq!(report.dte, Dte::Ready);
+        assert_eq!(report.assigned_mode, AssignedMode::Assigned);
+        assert!(!report.raim);
+    }
 }
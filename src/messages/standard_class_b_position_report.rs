@@ -18,6 +18,8 @@ pub struct StandardClassBPositionReport {
     pub position_accuracy: Accuracy,
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
     pub course_over_ground: Option<f32>,
     pub true_heading: Option<u16>,
     pub timestamp: u8,
@@ -31,6 +33,13 @@ pub struct StandardClassBPositionReport {
     pub radio_status: RadioStatus,
 }
 
+impl StandardClassBPositionReport {
+    /// Interprets the `timestamp` field, which can also carry the state of the positioning system
+    pub fn timestamp_status(&self) -> Timestamp {
+        Timestamp::parse(self.timestamp)
+    }
+}
+
 impl<'a> AisMessageType<'a> for StandardClassBPositionReport {
     fn name(&self) -> &'static str {
         "Standard Class B Position Report"
@@ -68,8 +77,8 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], StandardClassBPositionReport> {
         let (data, _regional_reserved) = take_bits::<_, u8, _, _>(8u8)(data)?;
         let (data, speed_over_ground) = map(take_bits(10u16), parse_speed_over_ground)(data)?;
         let (data, position_accuracy) = map(take_bits(1u8), Accuracy::parse)(data)?;
-        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
-        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 28)?;
+        let (data, raw_latitude) = signed_i32(data, 27)?;
         let (data, course_over_ground) = map(take_bits(12u16), parse_cog)(data)?;
         let (data, true_heading) = map(take_bits(9u16), parse_heading)(data)?;
         let (data, timestamp) = take_bits(6u8)(data)?;
@@ -95,8 +104,9 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], StandardClassBPositionReport> {
                 mmsi,
                 speed_over_ground,
                 position_accuracy,
-                longitude,
-                latitude,
+                longitude: parse_longitude(raw_longitude),
+                latitude: parse_latitude(raw_latitude),
+                coordinate: Coordinate::new(raw_latitude, raw_longitude),
                 course_over_ground,
                 true_heading,
                 timestamp,
@@ -150,4 +160,31 @@ mod tests {
             panic!("Expected SOTDMA message");
         }
     }
+    #[test]
+    fn test_heading_unavailable() {
+        let bitstream = pack_bits(&[
+            (18, 6),
+            (0, 2),
+            (338087471, 30),
+            (0, 8),
+            (62, 10),
+            (0, 1),
+            (-44_290_000i64 as u64, 28),
+            (24_420_000, 27),
+            (1830, 12),
+            (511, 9),
+            (21, 6),
+            (0, 2),
+            (1, 1),
+            (0, 4),
+            (0, 1),
+            (0, 1),
+            (1, 1),
+            (0, 19),
+        ]);
+        let report = StandardClassBPositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.mmsi, 338087471);
+        assert_eq!(report.course_over_ground, Some(183.0));
+        assert_eq!(report.true_heading, None);
+    }
 }
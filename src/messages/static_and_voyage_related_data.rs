@@ -1,5 +1,6 @@
 //! Static and Voyage Related Data (type 5)
 use super::parsers::*;
+use super::push_unwrap;
 use super::types::*;
 use super::AisMessageType;
 use crate::errors::Result;
@@ -32,6 +33,112 @@ pub struct StaticAndVoyageRelatedData {
     pub dte: Dte,
 }
 
+/// A field which can differ between two reports from the same vessel
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ChangedField {
+    AisVersion,
+    ImoNumber,
+    Callsign,
+    VesselName,
+    ShipType,
+    DimensionToBow,
+    DimensionToStern,
+    DimensionToPort,
+    DimensionToStarboard,
+    EpfdType,
+    /// Any part of the ETA
+    Eta,
+    Draught,
+    Destination,
+    Dte,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type ChangedFieldList = lib::std::vec::Vec<ChangedField>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type ChangedFieldList = lib::std::vec::Vec<ChangedField, 14>;
+
+impl StaticAndVoyageRelatedData {
+    /// The vessel's dimensions, noting any which are too big to be represented exactly
+    pub fn dimensions(&self) -> Dimensions {
+        Dimensions::new(
+            self.dimension_to_bow,
+            self.dimension_to_stern,
+            self.dimension_to_port,
+            self.dimension_to_starboard,
+        )
+    }
+
+    /// Looks up the destination as a UN/LOCODE, such as `NLRTM` for Rotterdam.
+    /// Destinations given as a route, like `DEHAM>NLRTM`, use the part after the
+    /// last `>`. Returns `None` for free text, or codes not in the bundled table.
+    #[cfg(feature = "locode")]
+    pub fn resolve_destination(&self) -> Option<super::locode::PortInfo> {
+        let destination = self.destination.rsplit('>').next()?;
+        super::locode::lookup(destination)
+    }
+
+    /// Lists the fields which differ between this report and `other`, in
+    /// transmission order. The message header, including the MMSI, isn't compared.
+    pub fn diff(&self, other: &Self) -> ChangedFieldList {
+        let mut changed = ChangedFieldList::default();
+        let mut check = |field, differs| {
+            if differs {
+                push_unwrap(&mut changed, field);
+            }
+        };
+        check(
+            ChangedField::AisVersion,
+            self.ais_version != other.ais_version,
+        );
+        check(ChangedField::ImoNumber, self.imo_number != other.imo_number);
+        check(ChangedField::Callsign, self.callsign != other.callsign);
+        check(
+            ChangedField::VesselName,
+            self.vessel_name != other.vessel_name,
+        );
+        check(ChangedField::ShipType, self.ship_type != other.ship_type);
+        check(
+            ChangedField::DimensionToBow,
+            self.dimension_to_bow != other.dimension_to_bow,
+        );
+        check(
+            ChangedField::DimensionToStern,
+            self.dimension_to_stern != other.dimension_to_stern,
+        );
+        check(
+            ChangedField::DimensionToPort,
+            self.dimension_to_port != other.dimension_to_port,
+        );
+        check(
+            ChangedField::DimensionToStarboard,
+            self.dimension_to_starboard != other.dimension_to_starboard,
+        );
+        check(ChangedField::EpfdType, self.epfd_type != other.epfd_type);
+        check(
+            ChangedField::Eta,
+            (
+                self.eta_month_utc,
+                self.eta_day_utc,
+                self.eta_hour_utc,
+                self.eta_minute_utc,
+            ) != (
+                other.eta_month_utc,
+                other.eta_day_utc,
+                other.eta_hour_utc,
+                other.eta_minute_utc,
+            ),
+        );
+        check(ChangedField::Draught, self.draught != other.draught);
+        check(
+            ChangedField::Destination,
+            self.destination != other.destination,
+        );
+        check(ChangedField::Dte, self.dte != other.dte);
+        changed
+    }
+}
+
 impl<'a> AisMessageType<'a> for StaticAndVoyageRelatedData {
     fn name(&self) -> &'static str {
         "Static and Voyage Related Data"
@@ -155,4 +262,91 @@ mod tests {
         assert_eq!(message.epfd_type, None);
         assert_eq!(message.dte, Dte::Ready);
     }
+
+    #[test]
+    fn test_saturated_dimensions() {
+        let bytestream = b"5341U9`00000uCGCKL0u=@T4000000000000001?<@<47u;b004Sm51DQ0C@";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let mut message = StaticAndVoyageRelatedData::parse(bitstream.as_ref()).unwrap();
+        assert!(!message.dimensions().is_saturated());
+        message.dimension_to_bow = 511;
+        let dimensions = message.dimensions();
+        assert!(dimensions.is_saturated());
+        assert_eq!(
+            dimensions.to_bow,
+            Dimension {
+                metres: 511,
+                saturated: true
+            }
+        );
+        assert!(!dimensions.to_stern.saturated);
+        assert!(!dimensions.to_port.saturated);
+    }
+
+    #[test]
+    fn test_dimensions_without_position_fix() {
+        // No EPFD type, so the sender has no position fix
+        let bitstream = crate::test_helpers::pack_bits(&[
+            (5, 6),
+            (0, 2),
+            (244660000, 30),
+            (0, 2),
+            (0, 30),
+            (0, 42),
+            (0, 120),
+            (70, 8),
+            (100, 9),
+            (20, 9),
+            (5, 6),
+            (7, 6),
+            (0, 4),
+            (0, 20),
+            (0, 8),
+            (0, 120),
+            (1, 1),
+            (0, 1),
+        ]);
+        let message = StaticAndVoyageRelatedData::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(message.epfd_type, None);
+        assert_eq!(message.dimensions(), Dimensions::new(100, 20, 5, 7));
+        assert_eq!(message.dimensions().to_bow.metres, 100);
+        assert_eq!(message.dimensions().to_starboard.metres, 7);
+    }
+
+    #[cfg(feature = "locode")]
+    #[test]
+    fn test_resolve_destination() {
+        let bytestream = b"5341U9`00000uCGCKL0u=@T4000000000000001?<@<47u;b004Sm51DQ0C@";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let mut report = StaticAndVoyageRelatedData::parse(bitstream.as_ref()).unwrap();
+        report.destination = "NLRTM".into();
+        let port = report.resolve_destination().unwrap();
+        assert_eq!(port.name, "Rotterdam");
+        assert!((51.0..52.0).contains(&port.latitude));
+        report.destination = "DEHAM>NLRTM".into();
+        assert_eq!(report.resolve_destination().unwrap().locode, "NLRTM");
+        report.destination = "ROTTERDAM".into();
+        assert_eq!(report.resolve_destination(), None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let bytestream = b"5341U9`00000uCGCKL0u=@T4000000000000001?<@<47u;b004Sm51DQ0C@";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let before = StaticAndVoyageRelatedData::parse(bitstream.as_ref()).unwrap();
+        let mut after = StaticAndVoyageRelatedData::parse(bitstream.as_ref()).unwrap();
+        assert!(before.diff(&after).is_empty());
+        after.destination = "ANTWERP".into();
+        assert_eq!(&before.diff(&after)[..], &[ChangedField::Destination]);
+        after.eta_day_utc = Some(1);
+        after.draught = 6.5;
+        assert_eq!(
+            &before.diff(&after)[..],
+            &[
+                ChangedField::Eta,
+                ChangedField::Draught,
+                ChangedField::Destination
+            ]
+        );
+    }
 }
@@ -8,11 +8,16 @@ use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
 use nom::IResult;
 
+/// Static data from a Class B transponder, which is sent in two separate
+/// messages; see [`MessagePart`](enum.MessagePart.html). Each report only holds
+/// one part, so building up the full picture of a vessel means matching parts A
+/// and B by MMSI, which is left to the caller.
 #[derive(Debug, PartialEq, Eq)]
 pub struct StaticDataReport {
     pub message_type: u8,
     pub repeat_indicator: u8,
     pub mmsi: u32,
+    /// Which part this is, from the part number field, and its contents
     pub message_part: MessagePart,
 }
 
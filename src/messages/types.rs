@@ -1,4 +1,5 @@
 //! Common data types
+use crate::lib::std::fmt;
 
 /// Electronic Position Fixing Device type. This is the
 /// type of device used for determining the object's
@@ -257,7 +258,10 @@ impl From<u8> for Dte {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// Whether a station is following its own reporting schedule, or one assigned
+/// to it by a competent authority with a Group Assignment Command (type 23) or
+/// Assigned Mode Command (type 16)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum AssignedMode {
     Autonomous,
     Assigned,
@@ -271,4 +275,199 @@ impl AssignedMode {
             _ => unreachable!(),
         }
     }
+
+    pub fn is_assigned(&self) -> bool {
+        *self == Self::Assigned
+    }
+}
+
+/// One distance from the position reference point to the edge of a vessel, in metres
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Dimension {
+    pub metres: u16,
+    /// Whether this is the largest value the field can carry, meaning the actual
+    /// distance is this or more
+    pub saturated: bool,
+}
+
+impl Dimension {
+    fn new(metres: u16, max: u16) -> Self {
+        Self {
+            metres,
+            saturated: metres >= max,
+        }
+    }
+}
+
+/// Distances from the position reference point to each edge of a vessel
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Dimensions {
+    pub to_bow: Dimension,
+    pub to_stern: Dimension,
+    pub to_port: Dimension,
+    pub to_starboard: Dimension,
+}
+
+impl Dimensions {
+    /// Bow and stern distances saturate at 511m, port and starboard at 63m
+    pub fn new(to_bow: u16, to_stern: u16, to_port: u16, to_starboard: u16) -> Self {
+        Self {
+            to_bow: Dimension::new(to_bow, 511),
+            to_stern: Dimension::new(to_stern, 511),
+            to_port: Dimension::new(to_port, 63),
+            to_starboard: Dimension::new(to_starboard, 63),
+        }
+    }
+
+    /// Whether any of the distances is saturated
+    pub fn is_saturated(&self) -> bool {
+        self.to_bow.saturated
+            || self.to_stern.saturated
+            || self.to_port.saturated
+            || self.to_starboard.saturated
+    }
+}
+
+/// Class of the mobile station which sent a message
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum StationClass {
+    /// Class A, as carried by larger commercial vessels
+    ClassA,
+    /// Class B, as carried by smaller vessels
+    ClassB,
+}
+
+/// A Maritime Mobile Service Identity, which identifies the station a message
+/// came from. The kind of station and its country are encoded in the digits,
+/// as laid out in ITU-R M.585.
+///
+/// # Example:
+/// ```
+/// use ais::messages::types::Mmsi;
+///
+/// let mmsi = Mmsi::from(2573345);
+/// assert!(mmsi.is_coast_station());
+/// assert_eq!(mmsi.country_code(), Some(257));
+/// assert_eq!(mmsi.to_string(), "002573345");
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct Mmsi(pub u32);
+
+impl Mmsi {
+    /// The digit `n` places from the left of the nine digit identity
+    fn digit(&self, n: u32) -> u32 {
+        self.0 / 10u32.pow(8 - n) % 10
+    }
+
+    /// The three digits from place `start`, counting from the left
+    fn mid_from(&self, start: u32) -> u16 {
+        (self.0 / 10u32.pow(6 - start) % 1000) as u16
+    }
+
+    /// The Maritime Identification Digits, which give the country the station is
+    /// registered in. `None` for identities which don't carry them, such as
+    /// search and rescue transmitters, or whose digits aren't a valid MID, which
+    /// starts with 2 to 7.
+    pub fn country_code(&self) -> Option<u16> {
+        if self.0 > 999_999_999 {
+            return None;
+        }
+        let start = match (self.digit(0), self.digit(1), self.digit(2)) {
+            (2..=7, _, _) => 0,
+            // Group ship station
+            (0, 1..=9, _) => 1,
+            // Coast station
+            (0, 0, _) => 2,
+            // SAR aircraft
+            (1, 1, 1) => 3,
+            // Craft associated with a parent ship, and aids to navigation
+            (9, 8 | 9, _) => 2,
+            _ => return None,
+        };
+        let mid = self.mid_from(start);
+        (200..=799).contains(&mid).then_some(mid)
+    }
+
+    /// Whether this is a coast station, whose identity is `00` followed by a
+    /// valid MID
+    pub fn is_coast_station(&self) -> bool {
+        self.0 <= 9_999_999 && self.country_code().is_some()
+    }
+
+    /// Whether this is an AIS base station. Base stations are given coast station
+    /// identities, so this is the same as [`is_coast_station()`](#method.is_coast_station).
+    pub fn is_base_station(&self) -> bool {
+        self.is_coast_station()
+    }
+
+    /// Whether this is a search and rescue aircraft, whose identity starts with
+    /// `111`
+    pub fn is_sar_aircraft(&self) -> bool {
+        (111_000_000..=111_999_999).contains(&self.0)
+    }
+}
+
+impl From<u32> for Mmsi {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Mmsi> for u32 {
+    fn from(value: Mmsi) -> u32 {
+        value.0
+    }
+}
+
+impl fmt::Display for Mmsi {
+    /// Formats the identity as nine digits, keeping any leading zeroes
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:09}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+
+    #[test]
+    fn test_ship_mmsi() {
+        let mmsi = Mmsi::from(265547250);
+        assert_eq!(mmsi.country_code(), Some(265));
+        assert!(!mmsi.is_coast_station());
+        assert!(!mmsi.is_base_station());
+        assert!(!mmsi.is_sar_aircraft());
+        assert_eq!(u32::from(mmsi), 265547250);
+        // Group ship station
+        assert_eq!(Mmsi(26554725).country_code(), Some(265));
+    }
+
+    #[test]
+    fn test_coast_station_mmsi() {
+        let mmsi = Mmsi(3669145);
+        assert!(mmsi.is_coast_station());
+        assert!(mmsi.is_base_station());
+        assert!(!mmsi.is_sar_aircraft());
+        assert_eq!(mmsi.country_code(), Some(366));
+        // 000000000, 000012345 and 000812345 have no valid MID after the `00`
+        for mmsi in [0, 12345, 812345] {
+            assert!(!Mmsi(mmsi).is_coast_station(), "{}", mmsi);
+            assert!(!Mmsi(mmsi).is_base_station(), "{}", mmsi);
+            assert_eq!(Mmsi(mmsi).country_code(), None, "{}", mmsi);
+        }
+    }
+
+    #[test]
+    fn test_sar_aircraft_mmsi() {
+        let mmsi = Mmsi(111265001);
+        assert!(mmsi.is_sar_aircraft());
+        assert!(!mmsi.is_coast_station());
+        assert_eq!(mmsi.country_code(), Some(265));
+        // Search and rescue transmitters and aids to navigation
+        assert_eq!(Mmsi(970012345).country_code(), None);
+        assert_eq!(Mmsi(992351234).country_code(), Some(235));
+        assert_eq!(Mmsi(1_000_000_000).country_code(), None);
+        assert_eq!(Mmsi(111865001).country_code(), None);
+    }
 }
@@ -23,6 +23,8 @@ pub struct UtcDateResponse {
     pub fix_quality: Accuracy,
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
+    /// The position at full precision, or `None` if it isn't available
+    pub coordinate: Option<Coordinate>,
     pub epfd_type: Option<EpfdType>,
     pub raim: bool,
     pub radio_status: RadioStatus,
@@ -51,8 +53,8 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], UtcDateResponse> {
         let (data, minute) = parse_minsec(data)?;
         let (data, second) = parse_minsec(data)?;
         let (data, fix_quality) = map(take_bits(1u8), Accuracy::parse)(data)?;
-        let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
-        let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, raw_longitude) = signed_i32(data, 28)?;
+        let (data, raw_latitude) = signed_i32(data, 27)?;
         let (data, epfd_type) = map(take_bits(4u8), EpfdType::parse)(data)?;
         let (data, _spare) = take_bits::<_, u8, _, _>(10u8)(data)?;
         let (data, raim) = map(take_bits(1u8), u8_to_bool)(data)?;
@@ -70,8 +72,9 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], UtcDateResponse> {
                 minute,
                 second,
                 fix_quality,
-                longitude,
-                latitude,
+                longitude: parse_longitude(raw_longitude),
+                latitude: parse_latitude(raw_latitude),
+                coordinate: Coordinate::new(raw_latitude, raw_longitude),
                 epfd_type,
                 raim,
                 radio_status,
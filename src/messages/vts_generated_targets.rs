@@ -0,0 +1,191 @@
+//! VTS-Generated/Synthetic Targets (type 8, DAC 1, FI 17)
+use super::parsers::*;
+use super::push_unwrap;
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Each target takes up this many bits of the payload
+const TARGET_SIZE_BITS: usize = 120;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type VtsTargetList = lib::std::vec::Vec<VtsTarget>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type VtsTargetList = lib::std::vec::Vec<VtsTarget, 4>;
+
+/// Targets tracked by a Vessel Traffic Service, broadcast on behalf of
+/// vessels which may not be transmitting AIS themselves
+#[derive(Debug, PartialEq)]
+pub struct VtsGeneratedTargets {
+    /// Up to four targets
+    pub targets: VtsTargetList,
+}
+
+impl VtsGeneratedTargets {
+    /// Parses the binary payload of a DAC 1, FI 17 binary broadcast message
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, targets) = parse_targets(data)?;
+        Ok(targets)
+    }
+}
+
+/// How a VTS target is identified
+#[derive(Debug, PartialEq, Eq)]
+pub enum TargetIdentifier {
+    Mmsi(u32),
+    Imo(u32),
+    Callsign(AsciiString),
+    Other(AsciiString),
+}
+
+impl TargetIdentifier {
+    fn parse(data: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        let (data, identifier_type) = take_bits(2u8)(data)?;
+        match identifier_type {
+            0 | 1 => {
+                let (data, _spare) = take_bits::<_, u16, _, _>(12u8)(data)?;
+                let (data, number) = take_bits(30u32)(data)?;
+                if identifier_type == 0 {
+                    Ok((data, Self::Mmsi(number)))
+                } else {
+                    Ok((data, Self::Imo(number)))
+                }
+            }
+            2 => map(|data| parse_6bit_ascii(data, 42), Self::Callsign)(data),
+            3 => map(|data| parse_6bit_ascii(data, 42), Self::Other)(data),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VtsTarget {
+    pub identifier: TargetIdentifier,
+    pub latitude: Option<f32>,
+    pub longitude: Option<f32>,
+    pub course_over_ground: Option<f32>,
+    pub timestamp: Option<u8>,
+    pub speed_over_ground: Option<f32>,
+}
+
+impl VtsTarget {
+    fn parse(data: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        let (data, identifier) = TargetIdentifier::parse(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(4u8)(data)?;
+        let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+        let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+        let (data, course_over_ground) = map(take_bits(9u16), |course| match course {
+            360 => None,
+            _ => Some(course as f32),
+        })(data)?;
+        let (data, timestamp) = parse_minsec(data)?;
+        let (data, speed_over_ground) = map(take_bits(8u8), |speed| match speed {
+            255 => None,
+            _ => Some(speed as f32),
+        })(data)?;
+        Ok((
+            data,
+            Self {
+                identifier,
+                latitude,
+                longitude,
+                course_over_ground,
+                timestamp,
+                speed_over_ground,
+            },
+        ))
+    }
+}
+
+fn parse_longitude_min_1000(data: i32) -> Option<f32> {
+    match data {
+        10_860_000 => None,
+        _ => Some(data as f32 / 60_000.0),
+    }
+}
+
+fn parse_latitude_min_1000(data: i32) -> Option<f32> {
+    match data {
+        5_460_000 => None,
+        _ => Some(data as f32 / 60_000.0),
+    }
+}
+
+fn parse_targets(data: &[u8]) -> IResult<&[u8], VtsGeneratedTargets> {
+    bits(move |mut data| -> IResult<_, _> {
+        let mut targets = VtsTargetList::default();
+        while remaining_bits(data) >= TARGET_SIZE_BITS && targets.len() < 4 {
+            let (remaining, target) = VtsTarget::parse(data)?;
+            push_unwrap(&mut targets, target);
+            data = remaining;
+        }
+        // Whatever is left over is padding
+        Ok(((<&[u8]>::default(), 0), VtsGeneratedTargets { targets }))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary_broadcast_message::{
+        BinaryBroadcastMessage, BroadcastApplicationData,
+    };
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::pack_bits;
+
+    #[test]
+    fn test_vts_targets() {
+        let bitstream = pack_bits(&[
+            (8, 6),
+            (0, 2),
+            (2442000, 30),
+            (0, 2),
+            (1, 10),
+            (17, 6),
+            // An MMSI target
+            (0, 2),
+            (244123456, 42),
+            (0, 4),
+            (3_114_000, 24),
+            (-246_000i64 as u64, 25),
+            (90, 9),
+            (30, 6),
+            (12, 8),
+            // A callsign target with everything unavailable
+            (2, 2),
+            (0b001010_000101_001100_001100_001111_000000_000000, 42),
+            (0, 4),
+            (5_460_000, 24),
+            (10_860_000, 25),
+            (360, 9),
+            (60, 6),
+            (255, 8),
+        ]);
+        let message = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        let targets = match message.application_data().unwrap() {
+            Some(BroadcastApplicationData::VtsGeneratedTargets(targets)) => targets.targets,
+            other => panic!("Expected VTS targets, got {:?}", other),
+        };
+        assert_eq!(targets.len(), 2);
+        let target = &targets[0];
+        assert_eq!(target.identifier, TargetIdentifier::Mmsi(244123456));
+        assert_eq!(target.latitude, Some(51.9));
+        assert_eq!(target.longitude, Some(-4.1));
+        assert_eq!(target.course_over_ground, Some(90.0));
+        assert_eq!(target.timestamp, Some(30));
+        assert_eq!(target.speed_over_ground, Some(12.0));
+        let target = &targets[1];
+        assert_eq!(
+            target.identifier,
+            TargetIdentifier::Callsign("JELLO".into())
+        );
+        assert_eq!(target.latitude, None);
+        assert_eq!(target.longitude, None);
+        assert_eq!(target.course_over_ground, None);
+        assert_eq!(target.timestamp, None);
+        assert_eq!(target.speed_over_ground, None);
+    }
+}
@@ -0,0 +1,1359 @@
+//! Reading AIS sentences from a stream of NMEA lines
+use crate::errors::{Error, Result};
+use crate::lib::std::boxed::Box;
+use crate::lib::std::fmt;
+use crate::lib::std::fs::File;
+use crate::lib::std::io::{self, BufRead, BufReader};
+use crate::lib::std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use crate::lib::std::path::Path;
+use crate::lib::std::time::Duration;
+use crate::messages::{self, AisMessage};
+use crate::sentence::{AisFragments, AisParser, AisSentence, PendingInfo};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+/// Iterates over the complete sentences in a stream of NMEA lines, such as a
+/// log file, along with the 1-based line number each came from.
+///
+/// Sentences which are only fragments of a larger message are collected
+/// internally, and the complete sentence is reported with the line number of
+/// its final fragment. Lines that fail to parse are reported as errors, and
+/// reading carries on with the next line. Blank lines are skipped. Lines may
+/// end in `\n`, `\r\n` or `\r`; see [`Lines`](struct.Lines.html).
+///
+/// Sentences wrapped in other framing can be unwrapped with
+/// [`preprocess()`](#method.preprocess) before they are parsed.
+///
+/// # Example:
+/// ```
+/// use ais::reader::AisReader;
+///
+/// let log = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\n";
+/// for (line_number, sentence) in AisReader::new(&log[..]) {
+///     assert_eq!(line_number, 1);
+///     assert!(sentence?.message.is_some());
+/// }
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+pub struct AisReader<R> {
+    lines: Lines<R>,
+    parser: AisParser,
+    decode: bool,
+    preprocessor: Option<Box<Preprocessor>>,
+    on_unsupported: Option<Box<UnsupportedHandler>>,
+    /// The last line read, as it was before preprocessing
+    line: Vec<u8>,
+    line_number: usize,
+    done: bool,
+}
+
+/// Extracts the NMEA sentence from a line; see [`AisReader::preprocess()`](struct.AisReader.html#method.preprocess)
+pub type Preprocessor = dyn FnMut(&[u8]) -> Option<&[u8]>;
+
+/// Receives sentences with message types this library can't decode; see
+/// [`AisReader::on_unsupported()`](struct.AisReader.html#method.on_unsupported)
+pub type UnsupportedHandler = dyn FnMut(u8, &[u8]);
+
+impl<R: fmt::Debug> fmt::Debug for AisReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AisReader")
+            .field("lines", &self.lines)
+            .field("parser", &self.parser)
+            .field("decode", &self.decode)
+            .field("preprocessor", &self.preprocessor.is_some())
+            .field("on_unsupported", &self.on_unsupported.is_some())
+            .field("line", &self.line)
+            .field("line_number", &self.line_number)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<R: BufRead> AisReader<R> {
+    /// Creates a reader which also decodes the AIS message in each sentence
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: Lines::new(reader),
+            parser: AisParser::new(),
+            decode: true,
+            preprocessor: None,
+            on_unsupported: None,
+            line: Vec::new(),
+            line_number: 0,
+            done: false,
+        }
+    }
+
+    /// Sets whether the AIS messages should be decoded, or only passed along raw
+    pub fn decode(mut self, decode: bool) -> Self {
+        self.decode = decode;
+        self
+    }
+
+    /// Passes each line through `preprocessor` before it is parsed, for instance
+    /// to strip a proprietary wrapper from around the `!AIVDM` sentence. Lines
+    /// for which it returns `None` are skipped.
+    pub fn preprocess<F>(mut self, preprocessor: F) -> Self
+    where
+        F: FnMut(&[u8]) -> Option<&[u8]> + 'static,
+    {
+        self.preprocessor = Some(Box::new(preprocessor));
+        self
+    }
+
+    /// Passes sentences whose message type can't be decoded by this library to
+    /// `on_unsupported`, along with their message type and the line which
+    /// completed them, rather than reporting them as errors. The reader then
+    /// carries on with the next line. Only applies when decoding.
+    pub fn on_unsupported<F>(mut self, on_unsupported: F) -> Self
+    where
+        F: FnMut(u8, &[u8]) + 'static,
+    {
+        self.on_unsupported = Some(Box::new(on_unsupported));
+        self
+    }
+
+    /// Makes the reader yield each result along with the line it came from,
+    /// rather than the line number. A complete fragmented message comes with its
+    /// final fragment, and errors with the line which caused them.
+    pub fn with_raw_lines(self) -> WithRawLines<R> {
+        WithRawLines { reader: self }
+    }
+
+    /// Lists the fragmented messages still waiting for more fragments. Once the
+    /// reader is exhausted, anything here was cut off by the end of the input.
+    pub fn pending(&self) -> Vec<PendingInfo> {
+        self.parser.pending()
+    }
+}
+
+impl<R: BufRead> Iterator for AisReader<R> {
+    type Item = (usize, Result<AisSentence>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line_number += 1;
+            self.line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(err) => {
+                    self.line.clear();
+                    // There's no telling whether another read would succeed
+                    self.done = true;
+                    return Some((self.line_number, Err(err.into())));
+                }
+            };
+            let line = match self.preprocessor.as_mut() {
+                Some(preprocessor) => match preprocessor(&self.line) {
+                    Some(line) => line,
+                    None => continue,
+                },
+                None => &self.line,
+            };
+            if line.is_empty() {
+                continue;
+            }
+            // With a handler for unsupported types, decoding happens here instead
+            let on_unsupported = self.on_unsupported.as_deref_mut().filter(|_| self.decode);
+            let decode = self.decode && on_unsupported.is_none();
+            match self.parser.parse(line, decode) {
+                Ok(AisFragments::Complete(mut sentence)) => {
+                    if let Some(on_unsupported) = on_unsupported {
+                        match decode_or_divert(&mut sentence, line, on_unsupported) {
+                            Ok(true) => {}
+                            Ok(false) => continue,
+                            Err(err) => return Some((self.line_number, Err(err))),
+                        }
+                    }
+                    return Some((self.line_number, Ok(sentence)));
+                }
+                Ok(AisFragments::Incomplete(_)) => continue,
+                Err(err) => return Some((self.line_number, Err(err))),
+            }
+        }
+        None
+    }
+}
+
+/// An [`AisReader`](struct.AisReader.html) which yields the raw line behind
+/// each result; see [`AisReader::with_raw_lines()`](struct.AisReader.html#method.with_raw_lines)
+#[derive(Debug)]
+pub struct WithRawLines<R> {
+    reader: AisReader<R>,
+}
+
+impl<R: BufRead> Iterator for WithRawLines<R> {
+    type Item = (Vec<u8>, Result<AisSentence>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, sentence) = self.reader.next()?;
+        Some((self.reader.line.clone(), sentence))
+    }
+}
+
+/// Splits a stream into lines ending in `\n`, `\r\n` or `\r`, which can be
+/// mixed. The line endings are removed, and a final line with no ending is
+/// still returned.
+#[derive(Debug)]
+pub struct Lines<R> {
+    reader: R,
+    /// The last line ended in `\r`, so a `\n` straight after belongs to it
+    after_cr: bool,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            after_cr: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Vec::new();
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Some(Err(err)),
+            };
+            if available.is_empty() {
+                return if line.is_empty() {
+                    None
+                } else {
+                    Some(Ok(line))
+                };
+            }
+            let start = match self.after_cr {
+                true if available[0] == b'\n' => 1,
+                _ => 0,
+            };
+            self.after_cr = false;
+            let end = available[start..]
+                .iter()
+                .position(|&byte| byte == b'\n' || byte == b'\r');
+            match end {
+                Some(end) => {
+                    line.extend_from_slice(&available[start..start + end]);
+                    self.after_cr = available[start + end] == b'\r';
+                    self.reader.consume(start + end + 1);
+                    return Some(Ok(line));
+                }
+                None => {
+                    line.extend_from_slice(&available[start..]);
+                    let consumed = available.len();
+                    self.reader.consume(consumed);
+                }
+            }
+        }
+    }
+}
+
+/// Decodes the message in `sentence`, unless its type is unsupported, in which
+/// case it goes to `on_unsupported` and `false` is returned
+fn decode_or_divert(
+    sentence: &mut AisSentence,
+    line: &[u8],
+    on_unsupported: &mut UnsupportedHandler,
+) -> Result<bool> {
+    let unarmored = messages::unarmor(&sentence.data, sentence.fill_bit_count as usize)?;
+    match messages::parse(&unarmored) {
+        Ok(message) => sentence.message = Some(message),
+        Err(Error::UnsupportedMessageType(message_type)) => {
+            on_unsupported(message_type, line);
+            return Ok(false);
+        }
+        Err(err) => return Err(err),
+    }
+    Ok(true)
+}
+
+/// Iterates over the AIS messages decoded from a stream of NMEA lines, for
+/// callers which don't need the rest of each sentence or its line number.
+///
+/// This is an [`AisReader`](struct.AisReader.html) underneath, so fragments are
+/// collected until their message is complete, and lines which fail to parse or
+/// decode are reported as errors without ending the iteration.
+///
+/// # Example:
+/// ```
+/// use ais::reader::SentenceIter;
+///
+/// let log = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\r\n\r\n";
+/// let messages: Vec<_> = SentenceIter::new(&log[..]).collect::<Result<_, _>>()?;
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].mmsi(), 265547250);
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SentenceIter<R> {
+    reader: AisReader<R>,
+}
+
+impl<R: BufRead> SentenceIter<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: AisReader::new(reader),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for SentenceIter<R> {
+    type Item = Result<AisMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, sentence) = self.reader.next()?;
+        Some(sentence.and_then(|sentence| {
+            sentence
+                .message
+                .ok_or_else(|| "Sentence was not decoded".into())
+        }))
+    }
+}
+
+/// Opens the file at `path` for reading with an [`AisReader`](struct.AisReader.html)
+pub fn decode_from_file<P: AsRef<Path>>(path: P) -> Result<AisReader<BufReader<File>>> {
+    Ok(AisReader::new(BufReader::new(File::open(path)?)))
+}
+
+/// Connects to the Unix domain socket at `path`, as used on some shipboard
+/// systems to distribute AIS, for reading with an [`AisReader`](struct.AisReader.html).
+/// Reading blocks until more data arrives, and ends when the other end closes the
+/// connection.
+#[cfg(unix)]
+pub fn decode_from_unix_socket<P: AsRef<Path>>(
+    path: P,
+) -> Result<AisReader<BufReader<UnixStream>>> {
+    Ok(AisReader::new(BufReader::new(UnixStream::connect(path)?)))
+}
+
+/// How a stream is reconnected after it closes or fails; see
+/// [`ReconnectingStream`](struct.ReconnectingStream.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Number of failed attempts in a row to reconnect before giving up, or `None`
+    /// to keep trying forever. With `Some(0)`, the stream isn't reconnected.
+    pub max_retries: Option<u32>,
+    /// How long to wait before the first attempt to reconnect. This doubles after
+    /// each failed attempt, up to `max_delay`.
+    pub initial_delay: Duration,
+    /// Longest wait between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for StreamOptions {
+    /// Keeps trying forever, waiting from one second up to a minute
+    fn default() -> Self {
+        Self {
+            max_retries: None,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl StreamOptions {
+    /// The waits before each attempt to reconnect, never longer than `max_delay`
+    fn delays(&self) -> impl Iterator<Item = Duration> {
+        let max_delay = self.max_delay;
+        std::iter::successors(Some(self.initial_delay.min(max_delay)), move |delay| {
+            Some(delay.saturating_mul(2).min(max_delay))
+        })
+    }
+}
+
+/// A stream which reconnects with `connect` when it is closed or fails, waiting
+/// longer after each failed attempt, so that a long running collector survives
+/// its upstream restarting. Once the retries run out, the last error is
+/// returned.
+///
+/// A line cut short by the disconnection is ended there, rather than being
+/// joined to the first line of the next connection, so that only the line which
+/// was cut short is lost.
+///
+/// # Example:
+/// ```
+/// use ais::reader::{AisReader, ReconnectingStream, StreamOptions};
+/// use std::io::{BufReader, Cursor};
+///
+/// let mut connections = 0;
+/// let stream = ReconnectingStream::new(
+///     || {
+///         connections += 1;
+///         match connections {
+///             1..=2 => Ok(Cursor::new(b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\n")),
+///             _ => Err(std::io::ErrorKind::ConnectionRefused.into()),
+///         }
+///     },
+///     StreamOptions {
+///         max_retries: Some(1),
+///         initial_delay: std::time::Duration::ZERO,
+///         ..Default::default()
+///     },
+/// )?;
+/// let sentences: Vec<_> = AisReader::new(BufReader::new(stream)).collect();
+/// // One sentence from each connection, then the error from the failed retry
+/// assert_eq!(sentences.len(), 3);
+/// assert!(sentences[2].1.is_err());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ReconnectingStream<S, F> {
+    connect: F,
+    options: StreamOptions,
+    stream: Option<S>,
+    /// The stream was lost, and is due to be reconnected on the next read
+    disconnected: bool,
+    /// The last byte read wasn't a line ending
+    mid_line: bool,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for ReconnectingStream<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectingStream")
+            .field("options", &self.options)
+            .field("stream", &self.stream)
+            .field("disconnected", &self.disconnected)
+            .field("mid_line", &self.mid_line)
+            .finish()
+    }
+}
+
+impl<S, F> ReconnectingStream<S, F>
+where
+    S: io::Read,
+    F: FnMut() -> io::Result<S>,
+{
+    /// Connects with `connect`. Only later connections are retried, so an error
+    /// connecting the first time is returned straight away.
+    pub fn new(mut connect: F, options: StreamOptions) -> io::Result<Self> {
+        let stream = connect()?;
+        Ok(Self {
+            connect,
+            options,
+            stream: Some(stream),
+            disconnected: false,
+            mid_line: false,
+        })
+    }
+
+    /// Tries to connect again until it works or the retries run out
+    fn reconnect(&mut self) -> io::Result<()> {
+        let mut delays = self.options.delays();
+        let mut failures = 0;
+        loop {
+            std::thread::sleep(delays.next().unwrap_or(self.options.max_delay));
+            match (self.connect)() {
+                Ok(stream) => {
+                    self.stream = Some(stream);
+                    return Ok(());
+                }
+                Err(err) => {
+                    failures += 1;
+                    if self.options.max_retries.is_some_and(|max| failures >= max) {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<S, F> io::Read for ReconnectingStream<S, F>
+where
+    S: io::Read,
+    F: FnMut() -> io::Result<S>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let stream = match &mut self.stream {
+                Some(stream) => stream,
+                None if self.disconnected => {
+                    self.disconnected = false;
+                    self.reconnect()?;
+                    continue;
+                }
+                None => return Ok(0),
+            };
+            let result = stream.read(buf);
+            let closed = match &result {
+                Ok(count) => *count == 0 && !buf.is_empty(),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => true,
+            };
+            if !closed {
+                if let Ok(count @ 1..) = result {
+                    self.mid_line = !matches!(buf[count - 1], b'\n' | b'\r');
+                }
+                return result;
+            }
+            self.stream = None;
+            if self.options.max_retries == Some(0) {
+                return result;
+            }
+            self.disconnected = true;
+            if self.mid_line && !buf.is_empty() {
+                // End the line that was cut short before reconnecting
+                self.mid_line = false;
+                buf[0] = b'\n';
+                return Ok(1);
+            }
+        }
+    }
+}
+
+/// Connects to an AIS feed over TCP at `address`, for reading with an
+/// [`AisReader`](struct.AisReader.html). If the connection is lost, it is
+/// reconnected as set out in `options`; see
+/// [`ReconnectingStream`](struct.ReconnectingStream.html).
+pub fn decode_from_tcp<A: ToSocketAddrs>(address: A, options: StreamOptions) -> Result<TcpReader> {
+    let addresses: Vec<SocketAddr> = address.to_socket_addrs()?.collect();
+    let connect: Box<TcpConnect> = Box::new(move || TcpStream::connect(&addresses[..]));
+    let stream = ReconnectingStream::new(connect, options)?;
+    Ok(AisReader::new(BufReader::new(stream)))
+}
+
+/// Opens a new TCP connection for a [`TcpReader`](type.TcpReader.html)
+pub type TcpConnect = dyn FnMut() -> io::Result<TcpStream> + Send;
+
+/// Reads from a TCP feed; see [`decode_from_tcp()`](fn.decode_from_tcp.html)
+pub type TcpReader = AisReader<BufReader<ReconnectingStream<TcpStream, Box<TcpConnect>>>>;
+
+/// Largest UDP datagram that can be received
+const MAX_DATAGRAM: usize = 65536;
+
+/// A UDP socket which reads the datagrams sent to it as one stream of lines. A
+/// datagram whose last line has no line ending is given one, so that it isn't
+/// joined to the next datagram.
+#[derive(Debug)]
+pub struct UdpStream {
+    socket: UdpSocket,
+    /// The datagram being read
+    datagram: Vec<u8>,
+    /// How much of `datagram` has been read
+    position: usize,
+}
+
+impl UdpStream {
+    /// Binds a socket to `address` to receive datagrams on
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        Ok(Self {
+            socket: UdpSocket::bind(address)?,
+            datagram: Vec::new(),
+            position: 0,
+        })
+    }
+}
+
+impl io::Read for UdpStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // Empty datagrams are skipped, since reading nothing would end the stream
+        while self.position == self.datagram.len() {
+            self.position = 0;
+            self.datagram.resize(MAX_DATAGRAM, 0);
+            match self.socket.recv(&mut self.datagram) {
+                Ok(count) => self.datagram.truncate(count),
+                Err(err) => {
+                    self.datagram.clear();
+                    return Err(err);
+                }
+            }
+            if !matches!(self.datagram.last(), None | Some(b'\n' | b'\r')) {
+                self.datagram.push(b'\n');
+            }
+        }
+        let count = buf.len().min(self.datagram.len() - self.position);
+        buf[..count].copy_from_slice(&self.datagram[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+/// Listens for an AIS feed sent as UDP datagrams to `address`, for reading with
+/// an [`AisReader`](struct.AisReader.html). UDP has no connection to lose, so
+/// reading blocks until more datagrams arrive. If the socket fails, it is bound
+/// again as set out in `options`; see
+/// [`ReconnectingStream`](struct.ReconnectingStream.html).
+pub fn decode_from_udp<A: ToSocketAddrs>(address: A, options: StreamOptions) -> Result<UdpReader> {
+    let addresses: Vec<SocketAddr> = address.to_socket_addrs()?.collect();
+    let connect: Box<UdpConnect> = Box::new(move || UdpStream::bind(&addresses[..]));
+    let stream = ReconnectingStream::new(connect, options)?;
+    Ok(AisReader::new(BufReader::new(stream)))
+}
+
+/// Binds a new socket for a [`UdpReader`](type.UdpReader.html)
+pub type UdpConnect = dyn FnMut() -> io::Result<UdpStream> + Send;
+
+/// Reads from a UDP feed; see [`decode_from_udp()`](fn.decode_from_udp.html)
+pub type UdpReader = AisReader<BufReader<ReconnectingStream<UdpStream, Box<UdpConnect>>>>;
+
+/// How long to wait between attempts to reopen a serial device that has gone away
+#[cfg(all(feature = "serial", unix))]
+const SERIAL_RECONNECT_DELAY: crate::lib::std::time::Duration =
+    crate::lib::std::time::Duration::from_secs(1);
+
+/// A serial device, such as a USB AIS receiver, set up for reading NMEA lines.
+/// See [`decode_from_serial()`](fn.decode_from_serial.html).
+///
+/// If the device goes away, for instance because it was unplugged, reading
+/// keeps trying to reopen it, once a second, until it comes back.
+#[cfg(all(feature = "serial", unix))]
+#[derive(Debug)]
+pub struct SerialPort {
+    path: crate::lib::std::path::PathBuf,
+    speed: libc::speed_t,
+    device: Option<File>,
+}
+
+#[cfg(all(feature = "serial", unix))]
+impl SerialPort {
+    /// Opens the device at `path`, setting it to raw mode at `baud` bits per
+    /// second, 8 data bits, no parity and one stop bit
+    pub fn open<P: AsRef<Path>>(path: P, baud: u32) -> Result<Self> {
+        let speed = match baud {
+            4800 => libc::B4800,
+            9600 => libc::B9600,
+            19200 => libc::B19200,
+            38400 => libc::B38400,
+            57600 => libc::B57600,
+            115200 => libc::B115200,
+            230400 => libc::B230400,
+            _ => return Err("Unsupported baud rate".into()),
+        };
+        let mut port = Self {
+            path: path.as_ref().into(),
+            speed,
+            device: None,
+        };
+        port.device = Some(port.connect()?);
+        Ok(port)
+    }
+
+    fn connect(&self) -> io::Result<File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        let device = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NOCTTY)
+            .open(&self.path)?;
+        let fd = device.as_raw_fd();
+        // SAFETY: `fd` is open for as long as `device` is, and `termios` is only
+        // read after `tcgetattr()` has filled it in
+        unsafe {
+            let mut termios = crate::lib::std::mem::zeroed::<libc::termios>();
+            if libc::tcgetattr(fd, &mut termios) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            libc::cfmakeraw(&mut termios);
+            termios.c_cflag |= libc::CLOCAL | libc::CREAD;
+            termios.c_cflag &= !(libc::CSTOPB | libc::PARENB);
+            // Block until at least one byte arrives
+            termios.c_cc[libc::VMIN] = 1;
+            termios.c_cc[libc::VTIME] = 0;
+            if libc::cfsetispeed(&mut termios, self.speed) != 0
+                || libc::cfsetospeed(&mut termios, self.speed) != 0
+                || libc::tcsetattr(fd, libc::TCSANOW, &termios) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(device)
+    }
+}
+
+#[cfg(all(feature = "serial", unix))]
+impl io::Read for SerialPort {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(device) = &mut self.device {
+                match device.read(buf) {
+                    Ok(0) if !buf.is_empty() => {}
+                    Ok(count) => return Ok(count),
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => {}
+                }
+                // The device has gone away
+                self.device = None;
+            }
+            // Wait before every attempt, so a device which opens but fails to
+            // read doesn't keep the CPU busy
+            std::thread::sleep(SERIAL_RECONNECT_DELAY);
+            self.device = self.connect().ok();
+        }
+    }
+}
+
+/// Opens the serial device at `path`, such as `/dev/ttyUSB0`, at `baud` bits per
+/// second for reading with an [`AisReader`](struct.AisReader.html). Reading
+/// carries on through disconnections; see [`SerialPort`](struct.SerialPort.html).
+///
+/// Supported baud rates are 4800, 9600, 19200, 38400, 57600, 115200 and 230400.
+#[cfg(all(feature = "serial", unix))]
+pub fn decode_from_serial<P: AsRef<Path>>(
+    path: P,
+    baud: u32,
+) -> Result<AisReader<BufReader<SerialPort>>> {
+    Ok(AisReader::new(BufReader::new(SerialPort::open(
+        path, baud,
+    )?)))
+}
+
+/// Decodes the AIS data in column `payload_column` (counting from 0) of a CSV
+/// file, such as a replay archive, along with the 1-based line number each
+/// message came from. The first line is taken to be a header, and skipped.
+///
+/// The column can hold complete NMEA sentences, which may be fragments of a
+/// larger message, bare armored payloads with no fill bits, or the raw bytes of
+/// the message written in hex after `0x`, such as `0x043F4FB7`. Sentences
+/// contain commas, so they need to be quoted.
+///
+/// Lines which can't be read or decoded are reported as errors, and reading
+/// carries on with the next line. Only if reading fails twice in a row does it
+/// stop, since the file is then unlikely to be readable at all.
+pub fn decode_from_csv<P: AsRef<Path>>(
+    path: P,
+    payload_column: usize,
+) -> Result<Vec<(usize, Result<AisMessage>)>> {
+    Ok(decode_csv(
+        BufReader::new(File::open(path)?),
+        payload_column,
+    ))
+}
+
+/// Decodes the lines of CSV from `reader`; see [`decode_from_csv()`](fn.decode_from_csv.html)
+fn decode_csv(reader: impl BufRead, payload_column: usize) -> Vec<(usize, Result<AisMessage>)> {
+    let mut parser = AisParser::new();
+    let mut results = Vec::new();
+    let mut read_failed = false;
+    for (idx, line) in Lines::new(reader).enumerate().skip(1) {
+        let line_number = idx + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(err) if read_failed => {
+                results.push((line_number, Err(err.into())));
+                break;
+            }
+            Err(err) => {
+                read_failed = true;
+                results.push((line_number, Err(err.into())));
+                continue;
+            }
+        };
+        read_failed = false;
+        if line.is_empty() {
+            continue;
+        }
+        let field = match csv_field(&line, payload_column) {
+            Some(field) => field,
+            None => {
+                results.push((line_number, Err("Missing CSV payload column".into())));
+                continue;
+            }
+        };
+        let message = if field.starts_with(b"!") || field.starts_with(b"\\") {
+            match parser.parse(&field, true) {
+                Ok(AisFragments::Complete(sentence)) => sentence
+                    .message
+                    .ok_or_else(|| "Sentence was not decoded".into()),
+                Ok(AisFragments::Incomplete(_)) => continue,
+                Err(err) => Err(err),
+            }
+        } else if let Some(hex) = field
+            .strip_prefix(b"0x")
+            .or_else(|| field.strip_prefix(b"0X"))
+        {
+            decode_hex(hex).and_then(|data| messages::parse(&data))
+        } else {
+            messages::unarmor(&field, 0).and_then(|data| messages::parse(&data))
+        };
+        results.push((line_number, message));
+    }
+    results
+}
+
+/// Converts pairs of hex digits to the bytes they stand for
+fn decode_hex(hex: &[u8]) -> Result<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return Err("Hex payload has an odd number of digits".into());
+    }
+    hex.chunks(2)
+        .map(|pair| {
+            let digit = |byte: u8| (byte as char).to_digit(16);
+            match (digit(pair[0]), digit(pair[1])) {
+                (Some(high), Some(low)) => Ok((high << 4 | low) as u8),
+                _ => Err("Invalid hex payload".into()),
+            }
+        })
+        .collect()
+}
+
+/// Extracts field `column` from a line of CSV, removing any quoting
+fn csv_field(line: &[u8], column: usize) -> Option<Vec<u8>> {
+    let mut field = Vec::new();
+    let mut current = 0;
+    let mut quoted = false;
+    let mut bytes = line.iter().copied().peekable();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'"' if quoted && bytes.peek() == Some(&b'"') => {
+                // An escaped quote
+                bytes.next();
+                if current == column {
+                    field.push(byte);
+                }
+            }
+            b'"' => quoted = !quoted,
+            b',' if !quoted => {
+                if current == column {
+                    return Some(field);
+                }
+                current += 1;
+            }
+            _ if current == column => field.push(byte),
+            _ => {}
+        }
+    }
+    if current == column {
+        Some(field)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Error;
+    use crate::lib::std::io::ErrorKind;
+    use crate::messages::AisMessage;
+
+    const LOG: &[u8] = b"!AIVDM,1,1,,B,E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100,0*01\r
+\r
+!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78\r
+!AIVDM,2,2,1,B,0000000,2*26\r
+!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*22\r
+!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21";
+
+    #[test]
+    fn line_numbers() {
+        let results: Vec<_> = AisReader::new(LOG).collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].0, 1);
+        assert!(matches!(
+            results[0].1,
+            Ok(AisSentence {
+                message: Some(AisMessage::AidToNavigationReport(_)),
+                ..
+            })
+        ));
+        // The fragmented message is reported at its last line
+        assert_eq!(results[1].0, 4);
+        assert!(matches!(
+            results[1].1,
+            Ok(AisSentence {
+                message: Some(AisMessage::StaticAndVoyageRelatedData(_)),
+                ..
+            })
+        ));
+        assert_eq!(results[2].0, 5);
+        assert!(matches!(results[2].1, Err(Error::Checksum { .. })));
+        assert_eq!(results[3].0, 6);
+        assert!(results[3].1.is_ok());
+    }
+
+    #[test]
+    fn missing_file() {
+        match decode_from_file("does/not/exist.nmea") {
+            Err(Error::Io { kind, .. }) => assert_eq!(kind, ErrorKind::NotFound),
+            other => panic!("Expected an I/O error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn collect_from_file() {
+        let path = std::env::temp_dir().join(format!("ais-log-{}.nmea", std::process::id()));
+        std::fs::write(&path, LOG).unwrap();
+        let messages: Vec<AisMessage> = decode_from_file(&path)
+            .unwrap()
+            .filter_map(|(_, sentence)| sentence.ok()?.message)
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], AisMessage::AidToNavigationReport(_)));
+        assert!(matches!(
+            messages[1],
+            AisMessage::StaticAndVoyageRelatedData(_)
+        ));
+        assert!(matches!(messages[2], AisMessage::BaseStationReport(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_from_unix_socket() {
+        use std::io::Write;
+        use std::os::unix::net::UnixListener;
+
+        let path = std::env::temp_dir().join(format!("ais-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let sender = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(LOG).unwrap();
+        });
+        let messages: Vec<AisMessage> = decode_from_unix_socket(&path)
+            .unwrap()
+            .filter_map(|(_, sentence)| sentence.ok()?.message)
+            .collect();
+        sender.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(
+            messages[1],
+            AisMessage::StaticAndVoyageRelatedData(_)
+        ));
+    }
+
+    const NO_DELAY: StreamOptions = StreamOptions {
+        max_retries: Some(3),
+        initial_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    #[test]
+    fn reconnect_stream() {
+        let mut attempts = 0;
+        let stream = ReconnectingStream::new(
+            || {
+                attempts += 1;
+                match attempts {
+                    // Fails twice before coming back
+                    1 | 4 => Ok(io::Cursor::new(LOG)),
+                    _ => Err(io::Error::from(ErrorKind::ConnectionRefused)),
+                }
+            },
+            NO_DELAY,
+        )
+        .unwrap();
+        let results: Vec<_> = AisReader::new(BufReader::new(stream)).collect();
+        // The last line of each connection has no line ending, but is still ended
+        // there rather than running into the next line
+        assert_eq!(results.len(), 9);
+        let decoded = results
+            .iter()
+            .filter(|(_, sentence)| sentence.is_ok())
+            .count();
+        assert_eq!(decoded, 6);
+        assert_eq!(results[3].0, 6);
+        assert!(results[3].1.is_ok());
+        assert_eq!(results[4].0, 7);
+        assert!(matches!(
+            results[4].1,
+            Ok(AisSentence {
+                message: Some(AisMessage::AidToNavigationReport(_)),
+                ..
+            })
+        ));
+        match &results.last().unwrap().1 {
+            Err(Error::Io { kind, .. }) => assert_eq!(*kind, ErrorKind::ConnectionRefused),
+            result => panic!("Expected the reconnection to fail, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let delays = |initial_delay, max_delay| -> Vec<_> {
+            StreamOptions {
+                max_retries: None,
+                initial_delay,
+                max_delay,
+            }
+            .delays()
+            .take(4)
+            .collect()
+        };
+        let secs = Duration::from_secs;
+        assert_eq!(
+            delays(secs(1), secs(5)),
+            [secs(1), secs(2), secs(4), secs(5)]
+        );
+        // The first wait is capped too
+        assert_eq!(delays(secs(10), secs(2)), [secs(2); 4]);
+        // Doubling saturates rather than overflowing
+        assert_eq!(delays(Duration::MAX, Duration::MAX), [Duration::MAX; 4]);
+        assert_eq!(
+            delays(Duration::MAX / 2 + secs(1), Duration::MAX)[1..],
+            [Duration::MAX; 3]
+        );
+    }
+
+    #[test]
+    fn reconnect_disabled() {
+        let options = StreamOptions {
+            max_retries: Some(0),
+            ..NO_DELAY
+        };
+        let mut attempts = 0;
+        let stream = ReconnectingStream::new(
+            || {
+                attempts += 1;
+                Ok(io::Cursor::new(LOG))
+            },
+            options,
+        )
+        .unwrap();
+        let results: Vec<_> = AisReader::new(BufReader::new(stream)).collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(attempts, 1);
+        assert!(ReconnectingStream::new(
+            || Err::<io::Empty, _>(ErrorKind::NotFound.into()),
+            options
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reconnect_tcp() {
+        use std::io::Write;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let sender = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                stream
+                    .write_all(b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\r\n")
+                    .unwrap();
+            }
+        });
+        let reader = decode_from_tcp(address, NO_DELAY).unwrap();
+        let results: Vec<_> = reader.collect();
+        sender.join().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].1.is_ok() && results[1].1.is_ok());
+        assert!(results[2].1.is_err());
+    }
+
+    #[test]
+    fn read_from_udp() {
+        // Find a free port to listen on
+        let address = UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let reader = decode_from_udp(address, NO_DELAY).unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        // One datagram with no line ending, an empty one, and one with a whole
+        // fragmented message
+        sender
+            .send_to(b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24", address)
+            .unwrap();
+        sender.send_to(b"", address).unwrap();
+        sender
+            .send_to(
+                b"!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78\r
+!AIVDM,2,2,1,B,0000000,2*26\r\n",
+                address,
+            )
+            .unwrap();
+        let messages: Vec<AisMessage> = reader
+            .take(2)
+            .map(|(_, sentence)| sentence.unwrap().message.unwrap())
+            .collect();
+        assert!(matches!(messages[0], AisMessage::PositionReport(_)));
+        assert!(matches!(
+            messages[1],
+            AisMessage::StaticAndVoyageRelatedData(_)
+        ));
+    }
+
+    #[cfg(all(feature = "serial", unix))]
+    #[test]
+    fn read_from_serial() {
+        use std::ffi::CStr;
+        use std::fs::File;
+        use std::io::Write;
+        use std::os::unix::io::FromRawFd;
+
+        // A pseudoterminal stands in for the receiver
+        // SAFETY: the name buffer outlives the calls which fill it in, and the
+        // master descriptor is handed over to a `File`
+        let (mut master, name) = unsafe {
+            let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            assert!(fd >= 0);
+            assert_eq!(libc::grantpt(fd), 0);
+            assert_eq!(libc::unlockpt(fd), 0);
+            let mut name = [0 as libc::c_char; 128];
+            assert_eq!(libc::ptsname_r(fd, name.as_mut_ptr(), name.len()), 0);
+            let name = CStr::from_ptr(name.as_ptr()).to_str().unwrap().to_string();
+            (File::from_raw_fd(fd), name)
+        };
+        let mut reader = decode_from_serial(&name, 38400).unwrap();
+        // Sent in pieces, to be put back together
+        let (first, rest) = LOG.split_at(40);
+        master.write_all(first).unwrap();
+        master.flush().unwrap();
+        master.write_all(rest).unwrap();
+        master.write_all(b"\r\n").unwrap();
+        let messages: Vec<AisMessage> = reader
+            .by_ref()
+            .filter_map(|(_, sentence)| sentence.ok()?.message)
+            .take(3)
+            .collect();
+        assert!(matches!(messages[0], AisMessage::AidToNavigationReport(_)));
+        assert!(matches!(messages[2], AisMessage::BaseStationReport(_)));
+
+        assert!(decode_from_serial(&name, 1234).is_err());
+        assert!(decode_from_serial("/dev/does-not-exist", 38400).is_err());
+    }
+
+    #[test]
+    fn strip_wrapper() {
+        let log = b"$PXYZA,1,rx3,!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24
+$PXYZB,heartbeat
+!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21";
+        let reader = AisReader::new(&log[..]).preprocess(|line| {
+            let start = line.iter().position(|&byte| byte == b'!')?;
+            Some(&line[start..])
+        });
+        let results: Vec<_> = reader.collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, 1);
+        assert!(matches!(
+            results[0].1,
+            Ok(AisSentence {
+                message: Some(AisMessage::PositionReport(_)),
+                ..
+            })
+        ));
+        assert_eq!(results[1].0, 3);
+    }
+
+    #[test]
+    fn truncated_input() {
+        let log = b"!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21
+!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78";
+        let mut reader = AisReader::new(&log[..]);
+        assert_eq!(reader.by_ref().count(), 1);
+        let pending = reader.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].message_id, Some(1));
+        assert_eq!(pending[0].received_fragments, [1]);
+    }
+
+    #[test]
+    fn csv_archive() {
+        let path = std::env::temp_dir().join(format!("ais-csv-{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "time,payload,station\r
+1696241893,13u?etPv2;0n:dDPwUM1U1Cb069D,rx1\r
+1696241894,\"!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78\",rx1\r
+1696241895,\"!AIVDM,2,2,1,B,0000000,2*26\",rx1\r
+1696241896,not a payload,rx2\r
+1696241897\r
+1696241898,0x043F4FB7C83E08B0362AC520FE57419414EA006254,rx3\r
+1696241899,0x043F4,rx3\r
+1696241900,0x043G,rx3\r
+",
+        )
+        .unwrap();
+        let results = decode_from_csv(&path, 1);
+        std::fs::remove_file(&path).unwrap();
+        let results = results.unwrap();
+        assert_eq!(results.len(), 7);
+        assert_eq!(results[0].0, 2);
+        assert!(matches!(results[0].1, Ok(AisMessage::PositionReport(_))));
+        assert_eq!(results[1].0, 4);
+        assert!(matches!(
+            results[1].1,
+            Ok(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+        assert_eq!(results[2].0, 5);
+        assert!(results[2].1.is_err());
+        assert_eq!(results[3].0, 6);
+        assert!(results[3].1.is_err());
+        assert_eq!(results[4].0, 7);
+        match &results[4].1 {
+            Ok(AisMessage::PositionReport(report)) => assert_eq!(report.mmsi, 265547250),
+            result => panic!("Expected a position report, got {:?}", result),
+        }
+        assert_eq!(results[5].0, 8);
+        assert!(results[5].1.is_err());
+        assert!(results[6].1.is_err());
+    }
+
+    #[test]
+    fn csv_read_error() {
+        /// Reads each of its chunks in turn, or fails where there's no chunk
+        struct Flaky<'a>(Vec<Option<&'a [u8]>>);
+
+        impl io::Read for Flaky<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    return Ok(0);
+                }
+                match self.0.remove(0) {
+                    Some(mut chunk) => chunk.read(buf),
+                    None => Err(ErrorKind::Other.into()),
+                }
+            }
+        }
+
+        let csv = Flaky(vec![
+            Some(b"payload\n13u?etPv2;0n:dDPwUM1U1Cb069D\n"),
+            None,
+            Some(b"13u?etPv2;0n:dDPwUM1U1Cb069D\n"),
+        ]);
+        let results = decode_csv(BufReader::with_capacity(64, csv), 0);
+        assert_eq!(results.len(), 3);
+        assert!(results[0].1.is_ok());
+        assert!(matches!(results[1].1, Err(Error::Io { .. })));
+        // Reading carries on after the error
+        assert_eq!(results[2].0, 4);
+        assert!(results[2].1.is_ok());
+
+        // But not after reading fails twice in a row
+        struct Broken;
+
+        impl io::Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(ErrorKind::Other.into())
+            }
+        }
+
+        let results = decode_csv(
+            BufReader::new(io::Read::chain(&b"payload\n"[..], Broken)),
+            0,
+        );
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, result)| result.is_err()));
+    }
+
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field(b"a,b,c", 1).unwrap(), b"b");
+        assert_eq!(csv_field(b"a,b,c", 2).unwrap(), b"c");
+        assert_eq!(csv_field(b"a,\"b,\"\"c\"\"\",d", 1).unwrap(), b"b,\"c\"");
+        assert_eq!(csv_field(b"a,b", 2), None);
+    }
+
+    #[test]
+    fn unsupported_callback() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        // Type 10, UTC/date inquiry, isn't supported
+        let log = b"!AIVDM,1,1,,B,:5MlU41GMK6@,0*6C
+!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let reader = AisReader::new(&log[..]).on_unsupported({
+            let seen = Rc::clone(&seen);
+            move |message_type, line| seen.borrow_mut().push((message_type, line.to_vec()))
+        });
+        let results: Vec<_> = reader.collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 2);
+        assert!(results[0].1.is_ok());
+        let seen = seen.borrow();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, 10);
+        assert_eq!(seen[0].1, &log[..31]);
+    }
+
+    #[test]
+    fn raw_lines() {
+        let lines: Vec<_> = LOG.split(|&byte| byte == b'\n').collect();
+        let results: Vec<_> = AisReader::new(LOG).with_raw_lines().collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].0, lines[0].strip_suffix(b"\r").unwrap());
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, lines[3].strip_suffix(b"\r").unwrap());
+        assert!(matches!(
+            results[1].1,
+            Ok(AisSentence {
+                message: Some(AisMessage::StaticAndVoyageRelatedData(_)),
+                ..
+            })
+        ));
+        assert_eq!(results[2].0, lines[4].strip_suffix(b"\r").unwrap());
+        assert!(matches!(results[2].1, Err(Error::Checksum { .. })));
+        assert_eq!(results[3].0, lines[5]);
+    }
+
+    #[test]
+    fn sentence_iter() {
+        let mut log = crate::tests::TEST_MESSAGES.join(&b"\r\n"[..]);
+        log.extend_from_slice(b"\r\n\r\n");
+        let messages: Vec<_> = SentenceIter::new(io::Cursor::new(log))
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(messages.len(), 7);
+        assert_eq!(messages[1].mmsi(), 3669145);
+        assert!(matches!(
+            messages[6],
+            AisMessage::StaticAndVoyageRelatedData(_)
+        ));
+
+        // Fragments of two messages at once, on different channels
+        let log =
+            b"!AIVDM,2,1,1,A,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7B
+!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78
+
+!AIVDM,2,2,1,A,0000000,2*25
+!AIVDM,2,2,1,B,0000000,2*26
+!AIVDM,2,2,1,B,0000000,2*27";
+        let results: Vec<_> = SentenceIter::new(&log[..]).collect();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(
+            results[0],
+            Ok(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+        assert!(matches!(
+            results[1],
+            Ok(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+        assert!(matches!(results[2], Err(Error::Checksum { .. })));
+    }
+
+    #[test]
+    fn line_endings() {
+        let lines = |input: &[u8], capacity| {
+            Lines::new(BufReader::with_capacity(capacity, input))
+                .map(|line| line.unwrap())
+                .collect::<Vec<_>>()
+        };
+        for capacity in [1, 2, 64] {
+            assert_eq!(
+                lines(b"a\r\n\r\nbc\rd\n\re", capacity),
+                [&b"a"[..], b"", b"bc", b"d", b"", b"e"]
+            );
+            assert_eq!(lines(b"a\r", capacity), [b"a"]);
+            assert!(lines(b"", capacity).is_empty());
+        }
+
+        let decoded = |line_ending: &str| {
+            let log = String::from_utf8_lossy(LOG).replace("\r\n", line_ending);
+            AisReader::new(log.as_bytes())
+                .map(|(line_number, sentence)| (line_number, sentence.map(|s| s.message)))
+                .collect::<Vec<_>>()
+        };
+        let expected = decoded("\r\n");
+        assert_eq!(expected.len(), 4);
+        assert_eq!(decoded("\n"), expected);
+        assert_eq!(decoded("\r"), expected);
+    }
+
+    #[test]
+    fn without_decoding() {
+        let results: Vec<_> = AisReader::new(LOG).decode(false).collect();
+        assert_eq!(results.len(), 4);
+        assert!(results[0].1.as_ref().unwrap().message.is_none());
+    }
+}
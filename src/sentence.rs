@@ -4,6 +4,7 @@ use crate::lib;
 
 use crate::errors::{Error, Result};
 use crate::messages::{self, AisMessage};
+use crate::tag_block::{TagBlock, TagBlockError};
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take, take_until};
 use nom::character::complete::{anychar, digit1};
@@ -66,6 +67,25 @@ pub enum TalkerId {
     /// Unknown talker ID
     Unknown,
 }
+impl TalkerId {
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::AB => Some("AB"),
+            Self::AD => Some("AD"),
+            Self::AI => Some("AI"),
+            Self::AN => Some("AN"),
+            Self::AR => Some("AR"),
+            Self::AS => Some("AS"),
+            Self::AT => Some("AT"),
+            Self::AX => Some("AX"),
+            Self::BS => Some("BS"),
+            Self::SA => Some("SA"),
+            Self::Unknown => None,
+        }
+    }
+}
+
 impl<'a> From<&'a [u8]> for TalkerId {
     fn from(talker_id: &'a [u8]) -> Self {
         match talker_id {
@@ -84,12 +104,78 @@ impl<'a> From<&'a [u8]> for TalkerId {
     }
 }
 
+/// AIS VHF radio channel
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Channel {
+    /// AIS 1 (161.975 MHz)
+    A,
+    /// AIS 2 (162.025 MHz)
+    B,
+    /// Missing or unrecognized channel
+    Unknown,
+}
+
+impl Channel {
+    /// The channel's frequency in Hz, if known
+    pub fn frequency(&self) -> Option<u32> {
+        match self {
+            Self::A => Some(161_975_000),
+            Self::B => Some(162_025_000),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl From<Option<char>> for Channel {
+    fn from(channel: Option<char>) -> Self {
+        match channel {
+            Some('A') => Self::A,
+            Some('B') => Self::B,
+            _ => Self::Unknown,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum AisFragments {
     Complete(AisSentence),
     Incomplete(AisSentence),
 }
 
+impl AisFragments {
+    /// The sentence, if it completes a message
+    ///
+    /// # Example:
+    /// ```
+    /// use ais::sentence::AisParser;
+    ///
+    /// let mut parser = AisParser::new();
+    /// let line = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+    /// if let Some(sentence) = parser.parse(line, true)?.complete() {
+    ///     assert_eq!(sentence.message.unwrap().mmsi(), 265547250);
+    /// }
+    /// # Ok::<(), ais::errors::Error>(())
+    /// ```
+    pub fn complete(self) -> Option<AisSentence> {
+        self.into()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Self::Complete(_))
+    }
+}
+
+/// Iterates over the complete sentence, if there is one, so fragments can be
+/// flattened into a stream of complete messages
+impl IntoIterator for AisFragments {
+    type Item = AisSentence;
+    type IntoIter = core::option::IntoIter<AisSentence>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.complete().into_iter()
+    }
+}
+
 impl From<AisFragments> for Option<AisSentence> {
     fn from(frag: AisFragments) -> Self {
         match frag {
@@ -108,17 +194,277 @@ impl From<AisFragments> for Result<AisSentence> {
     }
 }
 
-#[derive(Debug, Default)]
-pub struct AisParser {
+/// Default limit on the number of fragmented messages being reassembled at once
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub const MAX_PENDING_GROUPS: usize = 64;
+/// Limit on the number of fragmented messages being reassembled at once
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub const MAX_PENDING_GROUPS: usize = 4;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+type PendingGroupList = lib::std::vec::Vec<PendingGroup>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+type PendingGroupList = lib::std::vec::Vec<PendingGroup, MAX_PENDING_GROUPS>;
+
+/// The exact lines a sentence was parsed from, one per fragment
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type SourceLines = lib::std::vec::Vec<lib::std::vec::Vec<u8>>;
+
+/// Largest number of fragments a message can be split into, as the count is a
+/// single digit in the sentence
+const MAX_FRAGMENTS: usize = 9;
+
+/// Fragments received so far for a message which isn't complete yet
+#[derive(Debug)]
+struct PendingGroup {
     message_id: Option<u8>,
-    fragment_number: u8,
+    channel: Option<char>,
+    num_fragments: u8,
+    /// Bit `n - 1` is set once fragment `n` has been received
+    received: u16,
+    /// Fragment data in the order it arrived
     data: AisRawData,
+    /// Start and length of each fragment's data in `data`, by fragment number
+    spans: [(usize, usize); MAX_FRAGMENTS],
+    /// Taken from the last fragment
+    fill_bit_count: u8,
+    /// Source lines along with their fragment numbers, in the order they arrived
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    lines: lib::std::vec::Vec<(u8, lib::std::vec::Vec<u8>)>,
+    /// The first checksum mismatch among the fragments, if any
+    checksum_mismatch: Option<(u8, u8)>,
+    /// Number of sentences the parser had seen when the group was started
+    started: usize,
+}
+
+impl PendingGroup {
+    fn matches(&self, ais_sentence: &AisSentence) -> bool {
+        self.message_id == ais_sentence.message_id && self.channel == ais_sentence.channel
+    }
+
+    fn has_fragment(&self, fragment_number: u8) -> bool {
+        self.received & (1 << (fragment_number - 1)) != 0
+    }
+
+    fn is_complete(&self) -> bool {
+        self.received == (1 << self.num_fragments) - 1
+    }
+
+    /// The data of all the fragments, in fragment order
+    fn assemble(&self) -> Result<AisRawData> {
+        let mut data = AisRawData::default();
+        for &(start, len) in &self.spans[..self.num_fragments as usize] {
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            data.extend_from_slice(&self.data[start..start + len]);
+            #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+            data.extend_from_slice(&self.data[start..start + len])
+                .map_err(|_| Error::from("Vec is full on extend_from_slice"))?;
+        }
+        Ok(data)
+    }
+}
+
+/// A snapshot of an incomplete fragmented message, for diagnostics
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct PendingInfo {
+    /// Sequential message ID shared by the fragments
+    pub message_id: Option<u8>,
+    pub channel: Option<char>,
+    /// Total number of fragments expected
+    pub num_fragments: u8,
+    /// Fragment numbers received so far, in order
+    pub received_fragments: lib::std::vec::Vec<u8>,
+}
+
+/// The envelope fields of a sentence, for passing fragments which have already
+/// been taken apart to [`AisParser::parse_fragment()`](struct.AisParser.html#method.parse_fragment)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct FragmentInfo<'a> {
+    pub num_fragments: u8,
+    /// 1-based
+    pub fragment_number: u8,
+    /// Sequential message ID shared by the fragments of a message
+    pub message_id: Option<u8>,
+    pub channel: Option<char>,
+    /// The armored payload
+    pub payload: &'a [u8],
+    pub fill_bit_count: u8,
+}
+
+#[derive(Debug)]
+pub struct AisParser {
+    /// Oldest first
+    pending: PendingGroupList,
+    max_pending: usize,
+    dropped_groups: usize,
+    /// Number of sentences seen so far, for aging out incomplete messages
+    sentences: usize,
+    max_fragment_age: Option<usize>,
+    validate_checksum: bool,
+    require_tag_block: bool,
+    skip_leading_whitespace: bool,
+    /// For payloads which aren't armored with the standard alphabet
+    alphabet: Option<messages::SixBitAlphabet>,
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    retain_lines: bool,
+}
+
+impl Default for AisParser {
+    fn default() -> Self {
+        Self {
+            pending: PendingGroupList::default(),
+            max_pending: MAX_PENDING_GROUPS,
+            dropped_groups: 0,
+            sentences: 0,
+            max_fragment_age: None,
+            validate_checksum: true,
+            require_tag_block: false,
+            skip_leading_whitespace: true,
+            alphabet: None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            retain_lines: false,
+        }
+    }
+}
+
+/// Configures an [`AisParser`](struct.AisParser.html) for feeds which need
+/// stricter or more lenient handling than the defaults; see
+/// [`AisParser::builder()`](struct.AisParser.html#method.builder)
+///
+/// # Example:
+/// ```
+/// use ais::AisParser;
+///
+/// let mut parser = AisParser::builder().validate_checksum(false).build();
+/// let line = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*00";
+/// let sentence: Option<ais::sentence::AisSentence> = parser.parse(line, true)?.into();
+/// let sentence = sentence.unwrap();
+/// assert!(sentence.message.is_some());
+/// assert_eq!(sentence.checksum_mismatch(), Some((0x00, 0x24)));
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct AisParserBuilder {
+    parser: AisParser,
+}
+
+impl AisParserBuilder {
+    /// Sets how many fragmented messages can be reassembled at once; see
+    /// [`AisParser::with_max_pending()`](struct.AisParser.html#method.with_max_pending)
+    pub fn max_pending(mut self, max_pending: usize) -> Self {
+        self.parser.max_pending = AisParser::with_max_pending(max_pending).max_pending;
+        self
+    }
+
+    /// Sets how many more sentences can be parsed after the first fragment of a
+    /// message to arrive before the message is given up on as incomplete. There
+    /// is no limit by default, and incomplete messages are only dropped to make
+    /// room for new ones; see [`max_pending()`](#method.max_pending).
+    ///
+    /// Dropped messages are counted by
+    /// [`AisParser::dropped_groups()`](struct.AisParser.html#method.dropped_groups).
+    pub fn max_fragment_age(mut self, sentences: usize) -> Self {
+        self.parser.max_fragment_age = Some(sentences);
+        self
+    }
+
+    /// Sets whether sentences with the wrong checksum are rejected, which is the
+    /// default. If not, they are parsed anyway, and the mismatch is available
+    /// from [`AisSentence::checksum_mismatch()`](struct.AisSentence.html#method.checksum_mismatch).
+    /// This also goes for the checksum of a tag block.
+    pub fn validate_checksum(mut self, validate: bool) -> Self {
+        self.parser.validate_checksum = validate;
+        self
+    }
+
+    /// Sets whether sentences without a tag block are rejected, with
+    /// [`TagBlockError::Missing`](../tag_block/enum.TagBlockError.html#variant.Missing).
+    /// They are accepted by default. A tag block which is there but invalid is
+    /// always rejected, with the reason.
+    pub fn require_tag_block(mut self, require: bool) -> Self {
+        self.parser.require_tag_block = require;
+        self
+    }
+
+    /// Sets whether a UTF-8 byte order mark and any whitespace in front of a
+    /// sentence are skipped, which is the default. If not, lines have to start
+    /// with the tag block or the `!` or `$`.
+    pub fn skip_leading_whitespace(mut self, skip: bool) -> Self {
+        self.parser.skip_leading_whitespace = skip;
+        self
+    }
+
+    /// Sets the alphabet payloads are armored with, for proprietary feeds which
+    /// don't use the standard one
+    pub fn alphabet(mut self, alphabet: messages::SixBitAlphabet) -> Self {
+        self.parser.alphabet = Some(alphabet);
+        self
+    }
+
+    /// Sets whether to keep a copy of the lines each sentence was parsed from,
+    /// including any tag blocks, for archiving or retransmitting them exactly as
+    /// received; see [`AisSentence::source_lines()`](struct.AisSentence.html#method.source_lines)
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn retain_source_lines(mut self, retain: bool) -> Self {
+        self.parser.retain_lines = retain;
+        self
+    }
+
+    pub fn build(self) -> AisParser {
+        self.parser
+    }
 }
 
 impl AisParser {
     /// Creates a new `AisParser` instance
     pub fn new() -> Self {
-        Self::default()
+        Self::builder().build()
+    }
+
+    /// Starts configuring a parser, beginning with the same defaults as
+    /// [`new()`](#method.new)
+    pub fn builder() -> AisParserBuilder {
+        AisParserBuilder::default()
+    }
+
+    /// Creates a new `AisParser` instance which reassembles at most `max_pending`
+    /// fragmented messages at once. When another one starts, the oldest incomplete
+    /// message is dropped.
+    ///
+    /// Without an allocator, `max_pending` can be no more than
+    /// [`MAX_PENDING_GROUPS`](constant.MAX_PENDING_GROUPS.html).
+    pub fn with_max_pending(max_pending: usize) -> Self {
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        let max_pending = max_pending.min(MAX_PENDING_GROUPS);
+        Self {
+            max_pending: max_pending.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Number of incomplete fragmented messages which have been dropped so far,
+    /// to stay within the pending message limit, because they were too old, or
+    /// because a new message reused their message ID
+    pub fn dropped_groups(&self) -> usize {
+        self.dropped_groups
+    }
+
+    /// Lists the fragmented messages currently waiting for more fragments, oldest
+    /// first. Groups that stay here are likely missing a fragment.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn pending(&self) -> lib::std::vec::Vec<PendingInfo> {
+        self.pending
+            .iter()
+            .map(|group| PendingInfo {
+                message_id: group.message_id,
+                channel: group.channel,
+                num_fragments: group.num_fragments,
+                received_fragments: (1..=group.num_fragments)
+                    .filter(|&fragment_number| group.has_fragment(fragment_number))
+                    .collect(),
+            })
+            .collect()
     }
 
     /// Parses `line` as an NMEA sentence, checking the checksum and returning an
@@ -127,53 +473,232 @@ impl AisParser {
     /// If `decode` is `true`, the internal AIS message will also be parsed
     /// If it is `false`, then internal AIS messages will be ignored.
     /// In both cases, AIS data will be passed along raw.
+    /// The fragments of a message can arrive in any order, and interleaved with
+    /// other messages. They are matched up by their sequential message ID and
+    /// channel, and the message is complete once all of them have been received.
+    /// A UTF-8 byte order mark and whitespace in front of the sentence are
+    /// skipped, unless turned off with [`AisParserBuilder::skip_leading_whitespace()`](struct.AisParserBuilder.html#method.skip_leading_whitespace).
     pub fn parse(&mut self, line: &[u8], decode: bool) -> Result<AisFragments> {
-        let (_, (data, mut ais_sentence, checksum)) = parse_nmea_sentence(line)?;
-        Self::check_checksum(data, checksum)?;
-        if ais_sentence.has_more() {
-            if ais_sentence.fragment_number == 1 {
-                self.message_id = ais_sentence.message_id;
-                self.fragment_number = 0;
-                self.data = AisRawData::default();
+        #[allow(unused_mut)]
+        let mut ais_sentence = self.parse_envelope(line)?;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        if self.retain_lines {
+            ais_sentence.source_lines.push(line.into());
+        }
+        self.add_sentence(ais_sentence, decode)
+    }
+
+    /// Parses the NMEA envelope of a single sentence, checking its checksum, tag
+    /// block and report type as set up for this parser, without adding it to any
+    /// fragmented message
+    fn parse_envelope(&self, line: &[u8]) -> Result<AisSentence> {
+        let sentence = match self.skip_leading_whitespace {
+            true => skip_leading_whitespace(line),
+            false => line,
+        };
+        let (_, (data, mut ais_sentence, checksum, tag_block)) = parse_nmea_sentence(sentence)?;
+        match Self::check_checksum(data, checksum) {
+            Err(Error::Checksum { expected, found }) if !self.validate_checksum => {
+                ais_sentence.checksum_mismatch = Some((expected, found));
             }
-            self.verify_and_extend_data(&ais_sentence)?;
-            Ok(AisFragments::Incomplete(ais_sentence))
-        } else {
-            if ais_sentence.is_fragment() {
-                self.verify_and_extend_data(&ais_sentence)?;
-                let mut data = AisRawData::default();
-                lib::std::mem::swap(&mut data, &mut self.data);
-                ais_sentence.data = data;
+            result => {
+                result?;
+            }
+        }
+        ais_sentence.tag_block = match tag_block {
+            Some(tag_block) => Some(TagBlock::parse_checked(tag_block, self.validate_checksum)?),
+            None if self.require_tag_block => return Err(TagBlockError::Missing.into()),
+            None => None,
+        };
+        if ais_sentence.report_type == AisReportType::Unknown {
+            return Err("Unsupported sentence type; expected VDM or VDO".into());
+        }
+        Ok(ais_sentence)
+    }
+
+    /// Like [`parse()`](#method.parse), but for a fragment whose NMEA envelope has
+    /// already been parsed, such as one received over a structured transport. The
+    /// returned sentence has an unknown talker ID and report type.
+    pub fn parse_fragment(
+        &mut self,
+        fragment: &FragmentInfo,
+        decode: bool,
+    ) -> Result<AisFragments> {
+        if fragment.fill_bit_count > 5 {
+            return Err("Invalid fill bit count".into());
+        }
+        if fragment.fragment_number == 0 || fragment.fragment_number > fragment.num_fragments {
+            return Err("Invalid fragment number".into());
+        }
+        let (_, message_type) = messages::message_type(fragment.payload)?;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        let data = fragment.payload.into();
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        let data = fragment
+            .payload
+            .try_into()
+            .map_err(|_| Error::from("Payload too large"))?;
+        let ais_sentence = AisSentence {
+            talker_id: TalkerId::Unknown,
+            report_type: AisReportType::Unknown,
+            num_fragments: fragment.num_fragments,
+            fragment_number: fragment.fragment_number,
+            message_id: fragment.message_id,
+            channel: fragment.channel.map(normalize_channel),
+            data,
+            fill_bit_count: fragment.fill_bit_count,
+            message_type,
+            message: None,
+            tag_block: None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            source_lines: SourceLines::new(),
+            checksum_mismatch: None,
+        };
+        self.add_sentence(ais_sentence, decode)
+    }
+
+    /// Adds a parsed sentence to any message it is a fragment of
+    fn add_sentence(
+        &mut self,
+        mut ais_sentence: AisSentence,
+        decode: bool,
+    ) -> Result<AisFragments> {
+        self.sentences = self.sentences.wrapping_add(1);
+        self.drop_stale_groups();
+        if ais_sentence.is_fragment() {
+            let idx = self.add_fragment(&ais_sentence)?;
+            if !self.pending[idx].is_complete() {
+                return Ok(AisFragments::Incomplete(ais_sentence));
             }
-            if decode {
-                let unarmored =
-                    messages::unarmor(&ais_sentence.data, ais_sentence.fill_bit_count as usize)?;
-                ais_sentence.message = Some(messages::parse(&unarmored)?)
+            let group = self.pending.remove(idx);
+            ais_sentence.data = group.assemble()?;
+            ais_sentence.fill_bit_count = group.fill_bit_count;
+            ais_sentence.checksum_mismatch = group.checksum_mismatch;
+            let (_, message_type) = messages::message_type(&ais_sentence.data)?;
+            ais_sentence.message_type = message_type;
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            {
+                let mut lines = group.lines;
+                lines.sort_by_key(|(fragment_number, _)| *fragment_number);
+                ais_sentence.source_lines = lines.into_iter().map(|(_, line)| line).collect();
             }
-            Ok(AisFragments::Complete(ais_sentence))
         }
+        if decode {
+            let fill_bits = ais_sentence.fill_bit_count as usize;
+            let unarmored = match &self.alphabet {
+                Some(alphabet) => {
+                    messages::unarmor_with_alphabet(&ais_sentence.data, fill_bits, alphabet)?
+                }
+                None => messages::unarmor(&ais_sentence.data, fill_bits)?,
+            };
+            ais_sentence.message = Some(messages::parse(&unarmored)?)
+        }
+        Ok(AisFragments::Complete(ais_sentence))
+    }
+
+    /// Drops any incomplete messages which are older than the fragment age limit
+    fn drop_stale_groups(&mut self) {
+        let max_age = match self.max_fragment_age {
+            Some(max_age) => max_age,
+            None => return,
+        };
+        let sentences = self.sentences;
+        let before = self.pending.len();
+        self.pending
+            .retain(|group| sentences.wrapping_sub(group.started) <= max_age);
+        self.dropped_groups += before - self.pending.len();
     }
 
-    fn verify_and_extend_data(&mut self, ais_sentence: &AisSentence) -> Result<()> {
-        if self.message_id != ais_sentence.message_id {
-            return Err("Message ID out of sequence".into());
+    /// Adds the data from `ais_sentence` to its pending group, starting one if
+    /// needed, and returns the index of the group
+    fn add_fragment(&mut self, ais_sentence: &AisSentence) -> Result<usize> {
+        let fragment_number = ais_sentence.fragment_number;
+        let num_fragments = ais_sentence.num_fragments;
+        if fragment_number == 0 || fragment_number > num_fragments {
+            return Err("Invalid fragment number".into());
         }
-        if ais_sentence.fragment_number - self.fragment_number != 1 {
-            return Err("Fragment numbers out of sequence".into());
+        if num_fragments as usize > MAX_FRAGMENTS {
+            return Err("Too many fragments".into());
         }
-        self.fragment_number = ais_sentence.fragment_number;
+        let idx = match self
+            .pending
+            .iter()
+            .position(|group| group.matches(ais_sentence))
+        {
+            Some(idx)
+                if self.pending[idx].num_fragments == num_fragments
+                    && !self.pending[idx].has_fragment(fragment_number) =>
+            {
+                idx
+            }
+            // A first fragment which has already been received, or a different
+            // number of fragments, means the ID has been reused for a new message
+            Some(idx)
+                if fragment_number == 1 || self.pending[idx].num_fragments != num_fragments =>
+            {
+                self.pending.remove(idx);
+                self.dropped_groups += 1;
+                self.start_group(ais_sentence)
+            }
+            Some(_) => return Err("Duplicate fragment".into()),
+            None => self.start_group(ais_sentence),
+        };
+        let group = &mut self.pending[idx];
+        group.spans[fragment_number as usize - 1] = (group.data.len(), ais_sentence.data.len());
         #[cfg(any(feature = "std", feature = "alloc"))]
-        self.data.extend_from_slice(&ais_sentence.data);
+        group.data.extend_from_slice(&ais_sentence.data);
         #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-        self.data
+        group
+            .data
             .extend_from_slice(&ais_sentence.data)
             .map_err(|_| Error::from("Vec is full on extend_from_slice"))?;
-        Ok(())
+        group.received |= 1 << (fragment_number - 1);
+        if fragment_number == num_fragments {
+            group.fill_bit_count = ais_sentence.fill_bit_count;
+        }
+        group.checksum_mismatch = group.checksum_mismatch.or(ais_sentence.checksum_mismatch);
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        group.lines.extend(
+            ais_sentence
+                .source_lines
+                .iter()
+                .map(|line| (fragment_number, line.clone())),
+        );
+        Ok(idx)
+    }
+
+    /// Starts collecting a new fragmented message, making room if needed, and
+    /// returns the index of its group
+    fn start_group(&mut self, ais_sentence: &AisSentence) -> usize {
+        if self.pending.len() >= self.max_pending {
+            self.pending.remove(0);
+            self.dropped_groups += 1;
+        }
+        let group = PendingGroup {
+            message_id: ais_sentence.message_id,
+            channel: ais_sentence.channel,
+            num_fragments: ais_sentence.num_fragments,
+            received: 0,
+            data: AisRawData::default(),
+            spans: [(0, 0); MAX_FRAGMENTS],
+            fill_bit_count: 0,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            lines: lib::std::vec::Vec::new(),
+            checksum_mismatch: None,
+            started: self.sentences,
+        };
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.pending.push(group);
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        self.pending
+            .push(group)
+            .expect("pending groups were trimmed to capacity");
+        self.pending.len() - 1
     }
 
     /// Verifies the AIS sentence checksum
     fn check_checksum(sentence: &[u8], expected_checksum: u8) -> Result<u8> {
-        let received_checksum = sentence.iter().fold(0u8, |acc, &item| acc ^ item);
+        let received_checksum = nmea_checksum(sentence);
         if expected_checksum != received_checksum {
             Err(Error::Checksum {
                 expected: expected_checksum,
@@ -190,17 +715,55 @@ impl AisParser {
 pub struct AisSentence {
     pub talker_id: TalkerId,
     pub report_type: AisReportType,
+    /// How many sentences the message is split across
     pub num_fragments: u8,
+    /// Which of those this is, counting from 1. A complete sentence for a
+    /// fragmented message carries the number of its last fragment.
     pub fragment_number: u8,
+    /// Ties together the fragments of one message; usually only given for
+    /// fragmented messages
     pub message_id: Option<u8>,
+    /// The AIS channel, normalized to `A` or `B` if given as `1`/`2` or in lowercase
     pub channel: Option<char>,
     pub data: AisRawData,
     pub fill_bit_count: u8,
     pub message_type: u8,
     pub message: Option<AisMessage>,
+    /// The tag block in front of the sentence, if there was one
+    pub tag_block: Option<TagBlock>,
+    /// The lines the sentence was parsed from; see
+    /// [`source_lines()`](#method.source_lines)
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub source_lines: SourceLines,
+    /// The given and calculated checksums, if they differ; see
+    /// [`checksum_mismatch()`](#method.checksum_mismatch)
+    pub checksum_mismatch: Option<(u8, u8)>,
 }
 
 impl AisSentence {
+    /// The lines this sentence was parsed from, one for each fragment, byte for
+    /// byte. This is only filled in when the parser was set to
+    /// [`retain_source_lines()`](struct.AisParserBuilder.html#method.retain_source_lines),
+    /// and is otherwise empty.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn source_lines(&self) -> &[lib::std::vec::Vec<u8>] {
+        &self.source_lines
+    }
+
+    /// The checksum given in the sentence and the one calculated from its
+    /// contents, in that order, if they differ. This is only possible for
+    /// parsers which don't [`validate_checksum()`](struct.AisParserBuilder.html#method.validate_checksum).
+    /// For a fragmented message, this is the first mismatch among its fragments.
+    pub fn checksum_mismatch(&self) -> Option<(u8, u8)> {
+        self.checksum_mismatch
+    }
+
+    /// Whether this is a `VDO` sentence, reporting on the receiver's own vessel
+    /// rather than another station
+    pub fn is_own_ship(&self) -> bool {
+        self.report_type == AisReportType::VDO
+    }
+
     /// Returns whether there are more fragments to come
     pub fn has_more(&self) -> bool {
         self.fragment_number < self.num_fragments
@@ -210,6 +773,438 @@ impl AisSentence {
     pub fn is_fragment(&self) -> bool {
         self.num_fragments != 1
     }
+
+    /// Returns whether the message was reassembled from more than one fragment.
+    /// Unlike [`is_fragment()`](#method.is_fragment), this is meant for complete
+    /// sentences returned by the parser.
+    pub fn was_multipart(&self) -> bool {
+        self.num_fragments > 1
+    }
+
+    /// The AIS channel the sentence was received on
+    pub fn radio_channel(&self) -> Channel {
+        self.channel.into()
+    }
+
+    /// A key identifying where this sentence came from, for attributing or
+    /// deduplicating sentences merged from several receivers. It joins the tag
+    /// block source (`s:`), if there is one, and the talker ID with a `/`, as in
+    /// `rx1/AI`.
+    ///
+    /// Returns `None` if neither is known.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn source_id(&self) -> Option<lib::std::string::String> {
+        let source = self
+            .tag_block
+            .as_ref()
+            .and_then(|tag_block| tag_block.source.as_deref());
+        match (source, self.talker_id.as_str()) {
+            (Some(source), Some(talker_id)) => Some(lib::std::format!("{}/{}", source, talker_id)),
+            (Some(source), None) => Some(source.into()),
+            (None, Some(talker_id)) => Some(talker_id.into()),
+            (None, None) => None,
+        }
+    }
+
+    /// Formats the position in this sentence as an InfluxDB line protocol point,
+    /// named `measurement`. The MMSI is used as a tag, with latitude, longitude,
+    /// and speed and course over ground (where available) as fields. The receive
+    /// time from the tag block, if present, becomes the timestamp.
+    ///
+    /// Returns `None` if the message wasn't decoded, or has no position.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn to_line_protocol(&self, measurement: &str) -> Option<lib::std::string::String> {
+        use lib::std::fmt::Write;
+
+        let position = self.message.as_ref()?.as_position()?;
+        let measurement = measurement.replace(',', "\\,").replace(' ', "\\ ");
+        let mut line = lib::std::format!(
+            "{},mmsi={} lat={},lon={}",
+            measurement,
+            position.mmsi,
+            position.latitude?,
+            position.longitude?
+        );
+        // Writing to a String can't fail
+        if let Some(speed_over_ground) = position.speed_over_ground {
+            write!(line, ",sog={}", speed_over_ground).unwrap();
+        }
+        if let Some(course_over_ground) = position.course_over_ground {
+            write!(line, ",cog={}", course_over_ground).unwrap();
+        }
+        let timestamp = self
+            .tag_block
+            .as_ref()
+            .and_then(|tag_block| tag_block.receiver_timestamp)
+            .and_then(|seconds| seconds.checked_mul(1_000_000_000));
+        if let Some(timestamp) = timestamp {
+            write!(line, " {}", timestamp).unwrap();
+        }
+        Some(line)
+    }
+}
+
+/// Calculates the NMEA checksum of `data`, which should be everything between
+/// the `!` or `$` and the `*` of a sentence, or between the `\\` and the `*` of
+/// a tag block
+pub fn nmea_checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |acc, &item| acc ^ item)
+}
+
+/// Checks the `*HH` checksum at the end of an NMEA sentence, and of its tag
+/// block if it has one, without parsing anything else. A trailing line ending
+/// is ignored.
+pub fn verify_sentence_checksum(sentence: &str) -> bool {
+    validate_combined(sentence).is_valid()
+}
+
+/// Which of the checksums on a line are valid; see
+/// [`validate_combined()`](fn.validate_combined.html)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct CombinedValidity {
+    /// Whether the tag block checksum is valid, or `None` if there is no tag block
+    pub tag_block: Option<bool>,
+    pub sentence: bool,
+}
+
+impl CombinedValidity {
+    /// Returns whether every checksum on the line is valid
+    pub fn is_valid(&self) -> bool {
+        self.sentence && self.tag_block != Some(false)
+    }
+}
+
+/// Checks the tag block and sentence checksums on a line separately, so that a
+/// relay can tell which part is corrupt. Nothing else is parsed, and a trailing
+/// line ending is ignored. A tag block without its closing `\` is invalid, as
+/// is the sentence after it.
+///
+/// # Example:
+/// ```
+/// use ais::sentence::validate_combined;
+///
+/// let validity = validate_combined(
+///     "\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*25",
+/// );
+/// assert_eq!(validity.tag_block, Some(true));
+/// assert!(!validity.sentence);
+/// ```
+pub fn validate_combined(line: &str) -> CombinedValidity {
+    let mut sentence = line.trim_end_matches(['\r', '\n']);
+    let mut tag_block = None;
+    if let Some(rest) = sentence.strip_prefix('\\') {
+        let (block, rest) = match rest.split_once('\\') {
+            Some(parts) => parts,
+            None => {
+                return CombinedValidity {
+                    tag_block: Some(false),
+                    sentence: false,
+                }
+            }
+        };
+        tag_block = Some(verify_checksum_suffix(block));
+        sentence = rest;
+    }
+    CombinedValidity {
+        tag_block,
+        sentence: match sentence.strip_prefix(['!', '$']) {
+            Some(sentence) => verify_checksum_suffix(sentence),
+            None => false,
+        },
+    }
+}
+
+/// Checks `data*HH`, where `HH` is the checksum of `data` in hex
+fn verify_checksum_suffix(data: &str) -> bool {
+    let (body, expected) = match data.rsplit_once('*') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    // `from_str_radix()` would also accept a sign
+    expected.len() == 2
+        && expected.bytes().all(|byte| byte.is_ascii_hexdigit())
+        && u8::from_str_radix(expected, 16) == Ok(nmea_checksum(body.as_bytes()))
+}
+
+/// Longest armored payload carried by a single sentence
+#[cfg(any(feature = "std", feature = "alloc"))]
+const MAX_PAYLOAD_CHARS: usize = 60;
+
+/// Builds NMEA sentences out of structured messages, for instance to generate
+/// test data from field values rather than opaque payloads
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug)]
+pub struct SentenceBuilder {
+    talker_id: TalkerId,
+    report_type: AisReportType,
+    channel: Option<char>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Default for SentenceBuilder {
+    fn default() -> Self {
+        Self {
+            talker_id: TalkerId::AI,
+            report_type: AisReportType::VDM,
+            channel: Some('A'),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl SentenceBuilder {
+    /// Creates a builder for `!AIVDM` sentences on channel A
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn talker_id(mut self, talker_id: TalkerId) -> Self {
+        self.talker_id = talker_id;
+        self
+    }
+
+    pub fn report_type(mut self, report_type: AisReportType) -> Self {
+        self.report_type = report_type;
+        self
+    }
+
+    pub fn channel(mut self, channel: Option<char>) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Encodes `message` into a complete NMEA sentence, with checksum.
+    ///
+    /// Returns an error if the message type can't be encoded, if the talker ID or
+    /// report type is `Unknown`, or if the message doesn't fit in a single sentence.
+    pub fn build(&self, message: &AisMessage) -> Result<lib::std::string::String> {
+        let (payload, fill_bits) = messages::encode(message)?;
+        if payload.len() > MAX_PAYLOAD_CHARS {
+            return Err("Message too long for a single sentence".into());
+        }
+        self.sentence(1, 1, None, &payload, fill_bits)
+    }
+
+    /// Encodes `message` into as many NMEA sentences as it needs, such as the two
+    /// fragments of a type 5 message. Fragments share the sequential `message_id`,
+    /// which must be 0-9; a message which fits in one sentence is built just as
+    /// [`build()`](#method.build) would, without one.
+    ///
+    /// Returns an error if the message type can't be encoded, if the talker ID or
+    /// report type is `Unknown`, if `message_id` is over 9, or if the message
+    /// needs more than 9 sentences.
+    pub fn build_fragments(
+        &self,
+        message: &AisMessage,
+        message_id: u8,
+    ) -> Result<lib::std::vec::Vec<lib::std::string::String>> {
+        if message_id > 9 {
+            return Err("Sequential message ID must be 0-9".into());
+        }
+        let (payload, fill_bits) = messages::encode(message)?;
+        if payload.len() <= MAX_PAYLOAD_CHARS {
+            return Ok(lib::std::vec![
+                self.sentence(1, 1, None, &payload, fill_bits)?
+            ]);
+        }
+        let num_fragments = payload.len().div_ceil(MAX_PAYLOAD_CHARS);
+        if num_fragments > 9 {
+            return Err("Message too long for 9 sentences".into());
+        }
+        // Payloads are ASCII, so they can be split anywhere
+        let fragments = payload.as_bytes().chunks(MAX_PAYLOAD_CHARS);
+        fragments
+            .enumerate()
+            .map(|(idx, fragment)| {
+                let fragment_number = idx + 1;
+                let fragment = lib::std::str::from_utf8(fragment).unwrap();
+                // Fill bits only ever come at the end of the last fragment
+                let fill_bits = if fragment_number == num_fragments {
+                    fill_bits
+                } else {
+                    0
+                };
+                self.sentence(
+                    num_fragments,
+                    fragment_number,
+                    Some(message_id),
+                    fragment,
+                    fill_bits,
+                )
+            })
+            .collect()
+    }
+
+    fn sentence(
+        &self,
+        num_fragments: usize,
+        fragment_number: usize,
+        message_id: Option<u8>,
+        payload: &str,
+        fill_bits: usize,
+    ) -> Result<lib::std::string::String> {
+        let talker_id = match self.talker_id.as_str() {
+            Some(talker_id) => talker_id,
+            None => return Err("Unknown talker ID can't be encoded".into()),
+        };
+        let report_type = match self.report_type {
+            AisReportType::VDM => "VDM",
+            AisReportType::VDO => "VDO",
+            AisReportType::Unknown => return Err("Unknown report type can't be encoded".into()),
+        };
+        let body = lib::std::format!(
+            "{}{},{},{},{},{},{},{}",
+            talker_id,
+            report_type,
+            num_fragments,
+            fragment_number,
+            message_id
+                .map(|message_id| lib::std::format!("{}", message_id))
+                .unwrap_or_default(),
+            self.channel
+                .map(lib::std::string::String::from)
+                .unwrap_or_default(),
+            payload,
+            fill_bits
+        );
+        Ok(lib::std::format!(
+            "!{}*{:02X}",
+            body,
+            nmea_checksum(body.as_bytes())
+        ))
+    }
+}
+
+/// A quick check of whether `line` is shaped like an NMEA sentence, for
+/// filtering raw input before it is parsed: an optional tag block, then `!` or
+/// `$`, comma separated fields, and `*` followed by two hex digits. A trailing
+/// line ending is ignored.
+///
+/// The checksum isn't verified, and the fields aren't parsed, so a line which
+/// passes can still fail to parse.
+pub fn looks_like_ais(line: &[u8]) -> bool {
+    let mut line = line;
+    while let [rest @ .., b'\r' | b'\n'] = line {
+        line = rest;
+    }
+    if let [b'\\', rest @ ..] = line {
+        match rest.iter().position(|&byte| byte == b'\\') {
+            Some(end) => line = &rest[end + 1..],
+            None => return false,
+        }
+    }
+    match line {
+        [b'!' | b'$', body @ .., b'*', high, low] => {
+            body.contains(&b',') && high.is_ascii_hexdigit() && low.is_ascii_hexdigit()
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether the fragment numbers in `received` include every fragment
+/// from 1 to `total`, for use in custom reassembly logic. Duplicates and numbers
+/// out of range are ignored.
+///
+/// # Example:
+/// ```
+/// use ais::sentence::is_group_complete;
+///
+/// assert!(is_group_complete(&[2, 1], 2));
+/// assert!(!is_group_complete(&[1, 3], 3));
+/// ```
+pub fn is_group_complete(received: &[u8], total: u8) -> bool {
+    total > 0 && (1..=total).all(|fragment_number| received.contains(&fragment_number))
+}
+
+/// Strips a UTF-8 byte order mark, as at the start of some files, and any
+/// whitespace from the start of a line
+fn skip_leading_whitespace(line: &[u8]) -> &[u8] {
+    let line = line.strip_prefix(b"\xef\xbb\xbf").unwrap_or(line);
+    let start = line
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    &line[start..]
+}
+
+/// Decodes every message in a buffer of newline separated sentences, in the
+/// order they complete, using one parser for the whole buffer. Fragments are held
+/// until the rest of their message arrives, and any left incomplete at the end
+/// are dropped. Blank lines are skipped, and lines which fail to parse give an
+/// error in place of a message.
+///
+/// # Example:
+/// ```
+/// use ais::sentence::decode_all;
+///
+/// let input = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\r\n\
+/// !AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21\r\n";
+/// let messages = decode_all(input);
+/// assert_eq!(messages.len(), 2);
+/// assert_eq!(messages[0].as_ref().unwrap().mmsi(), 265547250);
+/// ```
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn decode_all(input: &[u8]) -> lib::std::vec::Vec<Result<AisMessage>> {
+    let mut parser = AisParser::new();
+    input
+        .split(|&byte| byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match parser.parse(line, true) {
+            Ok(AisFragments::Complete(sentence)) => sentence.message.map(Ok),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
+
+/// Decodes just the MMSI, latitude and longitude from a single sentence, for
+/// applications such as live maps which need nothing else. The rest of the
+/// message isn't decoded.
+///
+/// The sentence is checked just as [`AisParser::parse()`](struct.AisParser.html#method.parse)
+/// checks it by default. Returns `None` for sentences which it would reject, for
+/// fragments, for message types which don't carry a position (anything other
+/// than 1-4, 9, 11, 18, 19, 21 and 27), and when the position is unavailable.
+///
+/// # Example:
+/// ```
+/// use ais::sentence::decode_position_only;
+///
+/// let (mmsi, latitude, longitude) =
+///     decode_position_only(b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24").unwrap();
+/// assert_eq!(mmsi, 265547250);
+/// assert!((latitude - 57.661).abs() < 0.001);
+/// assert!((longitude - 11.833).abs() < 0.001);
+/// ```
+pub fn decode_position_only(line: &[u8]) -> Option<(u32, f64, f64)> {
+    let sentence = AisParser::new().parse_envelope(line).ok()?;
+    if sentence.is_fragment() {
+        return None;
+    }
+    let unarmored = messages::unarmor(&sentence.data, sentence.fill_bit_count as usize).ok()?;
+    let mut reader = messages::BitReader::new(&unarmored);
+    let message_type = reader.read_u32(6).ok()?;
+    // Bit offset of the longitude, its width, and the width of the latitude
+    // which follows it, for each type
+    let (offset, longitude_bits, latitude_bits, scale) = match message_type {
+        1..=3 | 9 => (61, 28, 27, 600_000.0),
+        4 | 11 => (79, 28, 27, 600_000.0),
+        18 | 19 => (57, 28, 27, 600_000.0),
+        21 => (164, 28, 27, 600_000.0),
+        27 => (44, 18, 17, 600.0),
+        _ => return None,
+    };
+    reader.skip(2).ok()?;
+    let mmsi = reader.read_u32(30).ok()?;
+    reader.skip(offset - reader.position()).ok()?;
+    let longitude = reader.read_i32(longitude_bits).ok()? as f64 / scale;
+    let latitude = reader.read_i32(latitude_bits).ok()? as f64 / scale;
+    // Unavailable positions are sent as 181 and 91 degrees
+    if longitude.abs() > 180.0 || latitude.abs() > 90.0 {
+        return None;
+    }
+    Some((mmsi, latitude, longitude))
 }
 
 /// Converts bytes representing an ASCII number to a string slice
@@ -234,7 +1229,7 @@ fn parse_ais_sentence(data: &[u8]) -> IResult<&[u8], AisSentence> {
     let (data, message_id) = opt(parse_u8_digit)(data)?;
     let (data, _) = tag(",")(data)?;
     let (data, channel_bytes) = take_until(",")(data)?;
-    let (_, channel) = opt(anychar)(channel_bytes)?;
+    let (_, channel) = opt(map(anychar, normalize_channel))(channel_bytes)?;
     let (data, _) = tag(",")(data)?;
     let (data, ais_data) = take_until(",")(data)?;
     let (data, _) = tag(",")(data)?;
@@ -262,18 +1257,36 @@ fn parse_ais_sentence(data: &[u8]) -> IResult<&[u8], AisSentence> {
             fill_bit_count,
             message_type,
             message: None,
+            tag_block: None,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            source_lines: SourceLines::new(),
+            checksum_mismatch: None,
         },
     ))
 }
 
-/// Named parser for an overall NMEA 0183 sentence
-fn parse_nmea_sentence(data: &[u8]) -> IResult<&[u8], (&[u8], AisSentence, u8)> {
-    let (data, _) = opt(delimited(tag("\\"), take_until("\\"), tag("\\")))(data)?;
+/// Some sources number the AIS channels, or use lowercase letters
+fn normalize_channel(channel: char) -> char {
+    match channel {
+        '1' | 'a' => 'A',
+        '2' | 'b' => 'B',
+        _ => channel,
+    }
+}
+
+/// An NMEA 0183 sentence split into its parts: the checksummed contents, the
+/// sentence itself, its checksum and the contents of any tag block in front
+type NmeaParts<'a> = (&'a [u8], AisSentence, u8, Option<&'a [u8]>);
+
+/// Named parser for an overall NMEA 0183 sentence. The tag block is left for
+/// the caller to parse, so its errors can be reported.
+fn parse_nmea_sentence(data: &[u8]) -> IResult<&[u8], NmeaParts<'_>> {
+    let (data, tag_block) = opt(delimited(tag("\\"), take_until("\\"), tag("\\")))(data)?;
     let (data, _) = alt((tag("!"), tag("$")))(data)?;
     let (data, raw) = peek(take_until("*"))(data)?;
     let (data, msg) = terminated(parse_ais_sentence, tag("*"))(data)?;
     let (data, checksum) = verify(hex_u32, |val| val <= &0xff)(data)?;
-    Ok((data, (raw, msg, checksum as u8)))
+    Ok((data, (raw, msg, checksum as u8, tag_block)))
 }
 
 #[cfg(test)]
@@ -297,6 +1310,371 @@ mod tests {
     const WITH_BAD_TAG_BLOCK: &[u8] =
         b"s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
 
+    #[test]
+    fn verify_checksums() {
+        let line = lib::std::str::from_utf8;
+        assert!(verify_sentence_checksum(line(GOOD_CHECKSUM).unwrap()));
+        assert!(!verify_sentence_checksum(line(BAD_CHECKSUM).unwrap()));
+        assert!(verify_sentence_checksum(
+            "\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\r\n"
+        ));
+        // Bad tag block checksum
+        assert!(!verify_sentence_checksum(
+            "\\s:2573345,c:1696241893*01\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24"
+        ));
+        assert!(verify_sentence_checksum("$AIVDM,1*4a"));
+        for line in ["", "!AIVDM", "AIVDM,1*4A", "!AIVDM,1*4", "!AIVDM,1*+J"] {
+            assert!(!verify_sentence_checksum(line), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn validate_combined_checksums() {
+        let sentence = "!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        assert_eq!(
+            validate_combined(sentence),
+            CombinedValidity {
+                tag_block: None,
+                sentence: true,
+            }
+        );
+        // Valid tag block, corrupt sentence
+        let validity = validate_combined(
+            "\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069E,0*24\r\n",
+        );
+        assert_eq!(validity.tag_block, Some(true));
+        assert!(!validity.sentence);
+        assert!(!validity.is_valid());
+        // Corrupt tag block, valid sentence
+        let validity = validate_combined(
+            "\\s:2573345,c:1696241894*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24",
+        );
+        assert_eq!(validity.tag_block, Some(false));
+        assert!(validity.sentence);
+        assert!(!validity.is_valid());
+
+        assert!(validate_combined(
+            "\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24"
+        )
+        .is_valid());
+        assert_eq!(
+            validate_combined("\\s:2573345*00!AIVDM,1*4A"),
+            CombinedValidity {
+                tag_block: Some(false),
+                sentence: false,
+            }
+        );
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn decode_all_test_messages() {
+        let mut input = lib::std::vec::Vec::new();
+        for line in crate::tests::TEST_MESSAGES {
+            input.extend_from_slice(line);
+            input.push(b'\n');
+        }
+        let messages = decode_all(&input);
+        assert_eq!(messages.len(), 7);
+        assert!(messages.iter().all(|message| message.is_ok()));
+        assert!(matches!(
+            messages[6],
+            Ok(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+
+        input.extend_from_slice(b"garbage\n\n!AIVDM,2,1,1,B,5,0*22");
+        let messages = decode_all(&input);
+        assert_eq!(messages.len(), 8);
+        assert!(messages[7].is_err());
+    }
+
+    #[test]
+    fn parse_custom_alphabet() {
+        let mut chars = *messages::SixBitAlphabet::STANDARD_CHARS;
+        for char in chars.iter_mut() {
+            *char += 1;
+        }
+        let standard = messages::SixBitAlphabet::default();
+        let alphabet = messages::SixBitAlphabet::new(&chars).unwrap();
+        let mut line = *b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*00";
+        for char in &mut line[AIS_START_IDX..AIS_START_IDX + 28] {
+            *char = chars[standard.value(*char).unwrap() as usize];
+        }
+        let checksum = nmea_checksum(&line[1..line.len() - 3]);
+        let hex = b"0123456789ABCDEF";
+        let len = line.len();
+        line[len - 2] = hex[(checksum >> 4) as usize];
+        line[len - 1] = hex[(checksum & 0xf) as usize];
+
+        let mut parser = AisParser::builder().alphabet(alphabet).build();
+        let sentence: Option<AisSentence> = parser.parse(&line, true).unwrap().into();
+        let message = sentence.unwrap().message.unwrap();
+        assert_eq!(message.mmsi(), 265547250);
+        assert!(AisParser::new().parse(&line, true).is_err());
+    }
+
+    #[test]
+    fn group_completeness() {
+        assert!(is_group_complete(&[1, 2, 3], 3));
+        assert!(is_group_complete(&[3, 1, 2], 3));
+        assert!(is_group_complete(&[1], 1));
+        // Missing the middle fragment
+        assert!(!is_group_complete(&[1, 3], 3));
+        // Duplicates don't stand in for a missing fragment
+        assert!(!is_group_complete(&[1, 1, 3], 3));
+        assert!(is_group_complete(&[1, 2, 2, 3], 3));
+        assert!(!is_group_complete(&[], 2));
+        assert!(!is_group_complete(&[], 0));
+    }
+
+    #[test]
+    fn looks_like_ais_lines() {
+        assert!(looks_like_ais(GOOD_CHECKSUM));
+        // The checksum isn't checked
+        assert!(looks_like_ais(BAD_CHECKSUM));
+        assert!(looks_like_ais(FRAGMENT_2));
+        assert!(looks_like_ais(
+            b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\r\n"
+        ));
+        assert!(looks_like_ais(
+            b"\\s:2573345,c:1671533231*08\\!BSVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*3d"
+        ));
+        assert!(looks_like_ais(b"$GPGGA,1*4F"));
+
+        assert!(!looks_like_ais(b""));
+        assert!(!looks_like_ais(b"\r\n"));
+        assert!(!looks_like_ais(b"hello, world"));
+        assert!(!looks_like_ais(
+            b"AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24"
+        ));
+        assert!(!looks_like_ais(
+            b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0"
+        ));
+        assert!(!looks_like_ais(
+            b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*2"
+        ));
+        assert!(!looks_like_ais(
+            b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*2G"
+        ));
+        assert!(!looks_like_ais(b"!AIVDM*24"));
+        assert!(!looks_like_ais(b"\\s:2573345*08!AIVDM,1,1,,A,0*24"));
+    }
+
+    #[test]
+    fn parse_structured_fragments() {
+        let mut parser = AisParser::new();
+        let first = FragmentInfo {
+            num_fragments: 2,
+            fragment_number: 1,
+            message_id: Some(1),
+            channel: Some('2'),
+            payload: b"53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000",
+            fill_bit_count: 0,
+        };
+        let result = parser.parse_fragment(&first, true).unwrap();
+        assert!(matches!(result, AisFragments::Incomplete(_)));
+        let second = FragmentInfo {
+            fragment_number: 2,
+            payload: b"0000000",
+            fill_bit_count: 2,
+            ..first
+        };
+        let sentence = match parser.parse_fragment(&second, true).unwrap() {
+            AisFragments::Complete(sentence) => sentence,
+            other => panic!("Expected a complete sentence, got {:?}", other),
+        };
+        assert_eq!(sentence.channel, Some('B'));
+        assert_eq!(sentence.talker_id, TalkerId::Unknown);
+
+        // The same message, parsed from text
+        parser.parse(FRAGMENT_1, true).unwrap();
+        let expected: Option<AisSentence> = parser.parse(FRAGMENT_2, true).unwrap().into();
+        assert_eq!(sentence.message, expected.unwrap().message);
+
+        let invalid = FragmentInfo {
+            fragment_number: 3,
+            ..first
+        };
+        assert!(parser.parse_fragment(&invalid, true).is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn retain_source_lines() {
+        let mut parser = AisParser::builder().retain_source_lines(true).build();
+        let first = match parser.parse(FRAGMENT_1, false).unwrap() {
+            AisFragments::Incomplete(sentence) => sentence,
+            other => panic!("Expected an incomplete sentence, got {:?}", other),
+        };
+        assert_eq!(first.source_lines(), [FRAGMENT_1]);
+        let sentence: Option<AisSentence> = parser.parse(FRAGMENT_2, false).unwrap().into();
+        assert_eq!(sentence.unwrap().source_lines(), [FRAGMENT_1, FRAGMENT_2]);
+
+        let line = b"\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let sentence: Option<AisSentence> = parser.parse(line, false).unwrap().into();
+        assert_eq!(sentence.unwrap().source_lines(), [line]);
+
+        let mut parser = AisParser::new();
+        let sentence: Option<AisSentence> = parser.parse(line, false).unwrap().into();
+        assert!(sentence.unwrap().source_lines().is_empty());
+    }
+
+    #[test]
+    fn position_only() {
+        let positions = crate::tests::TEST_MESSAGES.map(decode_position_only);
+        // Aids to navigation, base station reports and a fragmented type 5
+        assert!(positions[..6].iter().all(Option::is_some));
+        assert_eq!(positions[6..], [None, None]);
+        let (mmsi, latitude, longitude) = positions[1].unwrap();
+        assert_eq!(mmsi, 3669145);
+        let sentence: Option<AisSentence> = AisParser::new()
+            .parse(crate::tests::TEST_MESSAGES[1], true)
+            .unwrap()
+            .into();
+        let report = match sentence.unwrap().message {
+            Some(AisMessage::BaseStationReport(report)) => report,
+            other => panic!("Expected a base station report, got {:?}", other),
+        };
+        assert!((latitude - report.latitude.unwrap() as f64).abs() < 1e-5);
+        assert!((longitude - report.longitude.unwrap() as f64).abs() < 1e-5);
+
+        let line = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        assert_eq!(decode_position_only(line).unwrap().0, 265547250);
+        assert_eq!(decode_position_only(BAD_CHECKSUM), None);
+        assert_eq!(decode_position_only(b"garbage"), None);
+        // The same lines as AisParser::parse() accepts
+        let line = b"\xef\xbb\xbf !AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        assert_eq!(decode_position_only(line).unwrap().0, 265547250);
+        let line = b"\\s:2573345,c:1696241893*01\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        assert!(AisParser::new().parse(line, false).is_err());
+        assert_eq!(decode_position_only(line), None);
+        let line = b"!AIABK,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*33";
+        assert!(AisParser::new().parse(line, false).is_err());
+        assert_eq!(decode_position_only(line), None);
+        // Type 24 carries no position
+        assert_eq!(
+            decode_position_only(b"!AIVDM,1,1,,A,H42O55i18tMET00000000000000,2*6D"),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_after_bom_and_whitespace() {
+        let mut parser = AisParser::new();
+        let line = b"\xef\xbb\xbf!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let sentence: Option<AisSentence> = parser.parse(line, true).unwrap().into();
+        assert_eq!(sentence.unwrap().message.unwrap().mmsi(), 265547250);
+        let line = b" \t!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        assert!(parser.parse(line, true).is_ok());
+        let line = b"\xef\xbb\xbf  \\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let sentence: Option<AisSentence> = parser.parse(line, false).unwrap().into();
+        assert!(sentence.unwrap().tag_block.is_some());
+
+        let mut parser = AisParser::builder().skip_leading_whitespace(false).build();
+        assert!(parser
+            .parse(
+                b" \xef\xbb\xbf!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24",
+                true
+            )
+            .is_err());
+        assert!(parser
+            .parse(b" !AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24", true)
+            .is_err());
+    }
+
+    #[test]
+    fn checksum_strictness() {
+        let mut strict = AisParser::builder().validate_checksum(true).build();
+        assert!(matches!(
+            strict.parse(BAD_CHECKSUM, true),
+            Err(Error::Checksum {
+                expected: 0x8d,
+                found: 0x7a
+            })
+        ));
+
+        let mut lenient = AisParser::builder()
+            .validate_checksum(false)
+            .require_tag_block(false)
+            .build();
+        let sentence: Option<AisSentence> = lenient.parse(BAD_CHECKSUM, true).unwrap().into();
+        let sentence = sentence.unwrap();
+        assert!(sentence.message.is_some());
+        assert_eq!(sentence.checksum_mismatch(), Some((0x8d, 0x7a)));
+        let sentence: Option<AisSentence> = lenient.parse(GOOD_CHECKSUM, true).unwrap().into();
+        assert_eq!(sentence.unwrap().checksum_mismatch(), None);
+
+        // A mismatch in the first fragment carries over to the complete message
+        lenient
+            .parse(
+                b"!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*79",
+                true,
+            )
+            .unwrap();
+        let sentence: Option<AisSentence> = lenient.parse(FRAGMENT_2, true).unwrap().into();
+        assert_eq!(sentence.unwrap().checksum_mismatch(), Some((0x79, 0x78)));
+
+        let mut tagged = AisParser::builder().require_tag_block(true).build();
+        assert_eq!(
+            tagged.parse(GOOD_CHECKSUM, false),
+            Err(Error::TagBlock(TagBlockError::Missing))
+        );
+        assert!(tagged.parse(WITH_TAG_BLOCK, false).is_ok());
+        assert!(tagged.parse(WITH_BAD_TAG_BLOCK, false).is_err());
+    }
+
+    #[test]
+    fn tag_block_checksum_strictness() {
+        let line = b"\\s:2573345,c:1696241893*01\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        let mismatch = Err(Error::TagBlock(TagBlockError::ChecksumMismatch {
+            calculated: 0,
+            expected: 1,
+        }));
+        let mut tagged = AisParser::builder().require_tag_block(true).build();
+        assert_eq!(tagged.parse(line, false), mismatch);
+
+        let mut lenient = AisParser::builder()
+            .validate_checksum(false)
+            .require_tag_block(true)
+            .build();
+        let sentence: Option<AisSentence> = lenient.parse(line, false).unwrap().into();
+        let tag_block = sentence.unwrap().tag_block.unwrap();
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+        // Other problems with the tag block are still errors
+        let line = b"\\s:2573345,c:1696241893\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        assert_eq!(
+            lenient.parse(line, false),
+            Err(Error::TagBlock(TagBlockError::MissingChecksum))
+        );
+        assert_eq!(
+            lenient.parse(GOOD_CHECKSUM, false),
+            Err(Error::TagBlock(TagBlockError::Missing))
+        );
+    }
+
+    #[test]
+    fn parse_own_ship() {
+        let mut parser = AisParser::new();
+        let line = b"!AIVDO,1,1,,,13u?etPv2;0n:dDPwUM1U1Cb069D,0*67";
+        let sentence: Option<AisSentence> = parser.parse(line, true).unwrap().into();
+        let sentence = sentence.unwrap();
+        assert_eq!(sentence.report_type, AisReportType::VDO);
+        assert!(sentence.is_own_ship());
+        assert!(matches!(
+            sentence.message,
+            Some(AisMessage::PositionReport(_))
+        ));
+
+        let sentence: Option<AisSentence> = parser.parse(GOOD_CHECKSUM, false).unwrap().into();
+        assert!(!sentence.unwrap().is_own_ship());
+
+        let line = b"!AIVDX,1,1,,,13u?etPv2;0n:dDPwUM1U1Cb069D,0*70";
+        match parser.parse(line, true) {
+            Err(Error::Nmea { msg }) => assert!(msg.contains("VDM or VDO"), "{}", msg),
+            other => panic!("Expected an error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn parse_valid_structure() {
         let result = parse_ais_sentence(&GOOD_CHECKSUM[1..63]).unwrap();
@@ -319,6 +1697,10 @@ mod tests {
                 fill_bit_count: 0,
                 message_type: 17,
                 message: None,
+                tag_block: None,
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                source_lines: SourceLines::new(),
+                checksum_mismatch: None,
             }
         );
     }
@@ -350,6 +1732,10 @@ mod tests {
                 fill_bit_count: 0,
                 message_type: 17,
                 message: None,
+                tag_block: None,
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                source_lines: SourceLines::new(),
+                checksum_mismatch: None,
             }
         );
         assert_eq!((result.1).2, 122);
@@ -377,10 +1763,32 @@ mod tests {
                 fill_bit_count: 0,
                 message_type: 17,
                 message: None,
+                tag_block: None,
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                source_lines: SourceLines::new(),
+                checksum_mismatch: None,
             })
         );
     }
 
+    #[test]
+    fn complete_fragments() {
+        let mut parser = AisParser::new();
+        let frag1 = parser.parse(FRAGMENT_1, false).unwrap();
+        assert!(!frag1.is_complete());
+        assert_eq!(frag1.complete(), None);
+        let frag2 = parser.parse(FRAGMENT_2, false).unwrap();
+        assert!(frag2.is_complete());
+        let sentence = frag2.complete().unwrap();
+        assert_eq!(sentence.num_fragments, 2);
+
+        let frag1 = parser.parse(FRAGMENT_1, false).unwrap();
+        let frag2 = parser.parse(FRAGMENT_2, false).unwrap();
+        let mut complete = frag1.into_iter().chain(frag2);
+        assert_eq!(complete.next(), Some(sentence));
+        assert_eq!(complete.next(), None);
+    }
+
     #[test]
     fn parse_valid_checksum() {
         let mut parser = AisParser::new();
@@ -405,11 +1813,367 @@ mod tests {
         }
         if let AisFragments::Complete(complete) = frag2 {
             assert!(complete.num_fragments > 1);
+            assert_eq!(complete.num_fragments, 2);
+            assert_eq!(complete.fragment_number, 2);
+            assert_eq!(complete.message_id, Some(1));
+            assert_eq!(complete.channel, Some('B'));
         } else {
             panic!("Expected frag2 to be complete, but it was {:?}", frag2);
         }
     }
 
+    #[test]
+    fn parse_interleaved_fragments() {
+        let fragment_1_id_2 =
+            b"!AIVDM,2,1,2,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7B";
+        let fragment_2_id_2 = b"!AIVDM,2,2,2,B,0000000,2*25";
+        let mut parser = AisParser::new();
+        parser.parse(FRAGMENT_1, true).unwrap();
+        parser.parse(fragment_1_id_2, true).unwrap();
+        let result = parser.parse(FRAGMENT_2, true).unwrap();
+        assert!(matches!(result, AisFragments::Complete(_)));
+        let result = parser.parse(fragment_2_id_2, true).unwrap();
+        assert!(matches!(result, AisFragments::Complete(_)));
+        assert_eq!(parser.dropped_groups(), 0);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn source_id() {
+        let sentence: Result<AisSentence> = AisParser::new()
+            .parse(WITH_TAG_BLOCK, false)
+            .unwrap()
+            .into();
+        assert_eq!(sentence.unwrap().source_id().unwrap(), "2573345/AI");
+        let sentence: Result<AisSentence> =
+            AisParser::new().parse(GOOD_CHECKSUM, false).unwrap().into();
+        assert_eq!(sentence.unwrap().source_id().unwrap(), "AI");
+    }
+
+    #[test]
+    fn parse_class_b_reports() {
+        let mut parser = AisParser::new();
+        let sentence: Option<AisSentence> = parser
+            .parse(b"!AIVDM,1,1,,B,B6:hQDh0029Pt<4TAS003h6TSP00,0*25", true)
+            .unwrap()
+            .into();
+        match sentence.unwrap().message {
+            Some(AisMessage::StandardClassBPositionReport(report)) => {
+                assert_eq!(report.mmsi, 413933907);
+                assert_eq!(report.speed_over_ground, Some(0.0));
+                assert_eq!(report.course_over_ground, Some(0.0));
+                assert!(report.longitude.is_some() && report.latitude.is_some());
+                assert!(report.whole_band);
+                assert!(!report.accepts_message_22);
+            }
+            message => panic!("Expected a type 18 message, got {:?}", message),
+        }
+        let sentence: Option<AisSentence> = parser
+            .parse(
+                b"!AIVDM,1,1,,B,C6:ijoP00:9NNF4TEspILDN0Vc0jNc1WWV0000000000S2<6R20P,0*44",
+                true,
+            )
+            .unwrap()
+            .into();
+        match sentence.unwrap().message {
+            Some(AisMessage::ExtendedClassBPositionReport(report)) => {
+                assert_eq!(report.mmsi, 413954782);
+                assert_eq!(report.course_over_ground, Some(40.7));
+                assert_eq!(report.true_heading, Some(40));
+                assert_eq!(report.name, "SU YOU 333");
+                assert_eq!(report.dimension_to_bow, 35);
+                assert_eq!(report.dimension_to_port, 4);
+            }
+            message => panic!("Expected a type 19 message, got {:?}", message),
+        }
+    }
+
+    #[test]
+    fn was_multipart() {
+        let mut parser = AisParser::new();
+        parser.parse(FRAGMENT_1, true).unwrap();
+        let sentence: Result<AisSentence> = parser.parse(FRAGMENT_2, true).unwrap().into();
+        let sentence = sentence.unwrap();
+        assert!(matches!(
+            sentence.message,
+            Some(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+        assert!(sentence.was_multipart());
+        let line = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let sentence: Result<AisSentence> = parser.parse(line, true).unwrap().into();
+        assert!(!sentence.unwrap().was_multipart());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn pending_snapshot() {
+        let mut parser = AisParser::new();
+        assert!(parser.pending().is_empty());
+        parser.parse(FRAGMENT_1, true).unwrap();
+        assert_eq!(
+            parser.pending(),
+            [PendingInfo {
+                message_id: Some(1),
+                channel: Some('B'),
+                num_fragments: 2,
+                received_fragments: lib::std::vec![1],
+            }]
+        );
+        parser.parse(FRAGMENT_2, true).unwrap();
+        assert!(parser.pending().is_empty());
+        parser.parse(b"!AIVDM,3,3,4,A,0,2*20", false).unwrap();
+        assert_eq!(parser.pending()[0].received_fragments, [3]);
+    }
+
+    #[test]
+    fn parse_too_many_pending_fragments() {
+        let fragment_1_id_2 =
+            b"!AIVDM,2,1,2,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7B";
+        let fragment_2_id_2 = b"!AIVDM,2,2,2,B,0000000,2*25";
+        let fragment_1_id_3 =
+            b"!AIVDM,2,1,3,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7A";
+        let fragment_2_id_3 = b"!AIVDM,2,2,3,B,0000000,2*24";
+        let mut parser = AisParser::with_max_pending(2);
+        parser.parse(FRAGMENT_1, true).unwrap();
+        parser.parse(fragment_1_id_2, true).unwrap();
+        parser.parse(fragment_1_id_3, true).unwrap();
+        assert_eq!(parser.dropped_groups(), 1);
+        assert!(matches!(
+            parser.parse(fragment_2_id_2, true).unwrap(),
+            AisFragments::Complete(_)
+        ));
+        assert!(matches!(
+            parser.parse(fragment_2_id_3, true).unwrap(),
+            AisFragments::Complete(_)
+        ));
+        // The oldest message was evicted, so its final fragment waits for a first
+        // fragment which has already gone
+        assert!(matches!(
+            parser.parse(FRAGMENT_2, true).unwrap(),
+            AisFragments::Incomplete(_)
+        ));
+    }
+
+    #[test]
+    fn parse_fragments_out_of_order() {
+        let mut parser = AisParser::new();
+        assert!(matches!(
+            parser.parse(FRAGMENT_2, true).unwrap(),
+            AisFragments::Incomplete(_)
+        ));
+        let sentence: Option<AisSentence> = parser.parse(FRAGMENT_1, true).unwrap().into();
+        let sentence = sentence.unwrap();
+        assert_eq!(sentence.fill_bit_count, 2);
+        match sentence.message {
+            Some(AisMessage::StaticAndVoyageRelatedData(data)) => {
+                assert_eq!(data.vessel_name, "HAKUNAMA")
+            }
+            message => panic!("Expected a type 5 message, got {:?}", message),
+        }
+        assert_eq!(parser.dropped_groups(), 0);
+
+        // Three fragments, 3 then 1 then 2
+        let mut parser = AisParser::new();
+        parser.parse(b"!AIVDM,3,3,4,A,0,2*20", false).unwrap();
+        parser.parse(b"!AIVDM,3,1,4,A,5,0*25", false).unwrap();
+        let sentence: Option<AisSentence> = parser
+            .parse(b"!AIVDM,3,2,4,A,3,0*20", false)
+            .unwrap()
+            .into();
+        let sentence = sentence.unwrap();
+        assert_eq!(&sentence.data[..], b"530");
+        assert_eq!(sentence.fill_bit_count, 2);
+    }
+
+    #[test]
+    fn parse_interleaved_fragments_out_of_order() {
+        let fragment_1_id_2 =
+            b"!AIVDM,2,1,2,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7B";
+        let fragment_2_id_2 = b"!AIVDM,2,2,2,B,0000000,2*25";
+        let single = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let mut parser = AisParser::new();
+        parser.parse(fragment_2_id_2, true).unwrap();
+        parser.parse(FRAGMENT_1, true).unwrap();
+        assert!(matches!(
+            parser.parse(single, true).unwrap(),
+            AisFragments::Complete(_)
+        ));
+        let first: Option<AisSentence> = parser.parse(fragment_1_id_2, true).unwrap().into();
+        assert_eq!(first.unwrap().message_id, Some(2));
+        let second: Option<AisSentence> = parser.parse(FRAGMENT_2, true).unwrap().into();
+        let second = second.unwrap();
+        assert_eq!(second.message_id, Some(1));
+        assert!(second.message.is_some());
+        assert_eq!(parser.dropped_groups(), 0);
+    }
+
+    #[test]
+    fn drop_stale_fragments() {
+        let mut parser = AisParser::builder().max_fragment_age(2).build();
+        let single = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        parser.parse(FRAGMENT_1, true).unwrap();
+        parser.parse(single, true).unwrap();
+        assert!(matches!(
+            parser.parse(FRAGMENT_2, true).unwrap(),
+            AisFragments::Complete(_)
+        ));
+
+        parser.parse(FRAGMENT_1, true).unwrap();
+        parser.parse(single, true).unwrap();
+        parser.parse(single, true).unwrap();
+        assert_eq!(parser.dropped_groups(), 0);
+        // Fragment 2 is the third sentence after fragment 1, so it's too late
+        assert!(matches!(
+            parser.parse(FRAGMENT_2, true).unwrap(),
+            AisFragments::Incomplete(_)
+        ));
+        assert_eq!(parser.dropped_groups(), 1);
+    }
+
+    #[test]
+    fn parse_reused_message_id() {
+        let mut parser = AisParser::new();
+        parser.parse(FRAGMENT_1, true).unwrap();
+        // Another first fragment with the same ID starts a new message
+        parser.parse(FRAGMENT_1, true).unwrap();
+        assert_eq!(parser.dropped_groups(), 1);
+        assert!(matches!(
+            parser.parse(FRAGMENT_2, true).unwrap(),
+            AisFragments::Complete(_)
+        ));
+        // As does a different number of fragments
+        parser.parse(FRAGMENT_1, true).unwrap();
+        parser.parse(b"!AIVDM,3,2,1,B,0,0*25", false).unwrap();
+        assert_eq!(parser.dropped_groups(), 2);
+    }
+
+    #[test]
+    fn parse_fragment_going_backwards() {
+        // Fragment 2 arriving after fragment 3 used to underflow the sequence check
+        let mut parser = AisParser::new();
+        parser.parse(b"!AIVDM,4,1,1,A,5,0*27", false).unwrap();
+        parser.parse(b"!AIVDM,4,2,1,A,0,0*21", false).unwrap();
+        parser.parse(b"!AIVDM,4,3,1,A,0,0*20", false).unwrap();
+        assert!(parser.parse(b"!AIVDM,4,2,1,A,0,0*21", false).is_err());
+    }
+
+    #[test]
+    fn parse_arbitrary_bytes() {
+        let mut parser = AisParser::new();
+        for line in [
+            &b""[..],
+            b"!",
+            b"!AIVDM,1,1,,A,,0*26",
+            b"!AIVDM,1,1,,A,\xff,0*D9",
+            b"!AIVDM,0,0,,A,5,0*13",
+            b"\\\\!AIVDM",
+        ] {
+            assert!(parser.parse(line, true).is_err());
+        }
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn build_position_report() {
+        use crate::lib::std::string::String;
+        use crate::messages::navigation::Accuracy;
+        use crate::messages::position_report::{NavigationStatus, PositionReport};
+        use crate::messages::radio_status::{RadioStatus, SotdmaMessage, SubMessage, SyncState};
+        use crate::test_helpers::f32_equal_naive;
+
+        let report = PositionReport {
+            message_type: 1,
+            repeat_indicator: 0,
+            mmsi: 366123456,
+            navigation_status: NavigationStatus::UnderWayUsingEngine,
+            rate_of_turn: None,
+            speed_over_ground: Some(12.3),
+            position_accuracy: Accuracy::Dgps,
+            longitude: Some(-122.4),
+            latitude: Some(37.8),
+            coordinate: None,
+            course_over_ground: Some(270.5),
+            true_heading: Some(271),
+            timestamp: 30,
+            maneuver_indicator: None,
+            raim: false,
+            radio_status: RadioStatus::Sotdma(SotdmaMessage {
+                sync_state: SyncState::UtcDirect,
+                slot_timeout: 2,
+                sub_message: SubMessage::SlotNumber(1234),
+            }),
+        };
+        let line: String = SentenceBuilder::new()
+            .channel(Some('B'))
+            .build(&AisMessage::PositionReport(report))
+            .unwrap();
+        assert!(line.starts_with("!AIVDM,1,1,,B,"));
+        let sentence = match AisParser::new().parse(line.as_bytes(), true).unwrap() {
+            AisFragments::Complete(sentence) => sentence,
+            AisFragments::Incomplete(_) => panic!("Expected a complete sentence"),
+        };
+        assert_eq!(sentence.channel, Some('B'));
+        let position = match sentence.message {
+            Some(AisMessage::PositionReport(position)) => position,
+            other => panic!("Expected a position report, got {:?}", other),
+        };
+        assert_eq!(position.mmsi, 366123456);
+        f32_equal_naive(position.latitude.unwrap(), 37.8);
+        f32_equal_naive(position.longitude.unwrap(), -122.4);
+        assert_eq!(position.speed_over_ground, Some(12.3));
+        assert_eq!(position.rate_of_turn, None);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn build_fragments() {
+        let mut parser = AisParser::new();
+        parser.parse(FRAGMENT_1, true).unwrap();
+        let expected = match parser.parse(FRAGMENT_2, true).unwrap() {
+            AisFragments::Complete(sentence) => sentence.message.unwrap(),
+            AisFragments::Incomplete(_) => panic!("Expected a complete sentence"),
+        };
+        let lines = SentenceBuilder::new()
+            .channel(Some('B'))
+            .build_fragments(&expected, 1)
+            .unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("!AIVDM,2,1,1,B,"));
+        assert!(lines[1].starts_with("!AIVDM,2,2,1,B,"));
+        assert!(lines.iter().all(|line| line.len() <= 82));
+        assert!(matches!(
+            parser.parse(lines[0].as_bytes(), true).unwrap(),
+            AisFragments::Incomplete(_)
+        ));
+        let decoded = match parser.parse(lines[1].as_bytes(), true).unwrap() {
+            AisFragments::Complete(sentence) => sentence.message.unwrap(),
+            AisFragments::Incomplete(_) => panic!("Expected a complete sentence"),
+        };
+        assert_eq!(decoded, expected);
+        assert!(SentenceBuilder::new().build(&expected).is_err());
+        assert!(SentenceBuilder::new().build_fragments(&expected, 9).is_ok());
+        assert!(SentenceBuilder::new()
+            .build_fragments(&expected, 10)
+            .is_err());
+
+        // Short messages don't need a message ID
+        let bitstream = messages::unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let message = messages::parse(&bitstream).unwrap();
+        let lines = SentenceBuilder::new().build_fragments(&message, 1).unwrap();
+        assert_eq!(lines, [SentenceBuilder::new().build(&message).unwrap()]);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn build_unknown_talker() {
+        let bitstream = messages::unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let message = messages::parse(&bitstream).unwrap();
+        assert!(SentenceBuilder::new()
+            .talker_id(TalkerId::Unknown)
+            .build(&message)
+            .is_err());
+    }
+
     #[test]
     fn test_talker_id_conversions() {
         assert_eq!(TalkerId::from(b"AI".as_ref()), TalkerId::AI);
@@ -424,13 +2188,42 @@ mod tests {
         assert_eq!(sentence.channel, None);
     }
 
+    #[test]
+    fn test_channel_normalization() {
+        for (line, channel) in [
+            (&b"!AIVDM,1,1,,1,13u?etPv2;0n:dDPwUM1U1Cb069D,0*54"[..], 'A'),
+            (b"!AIVDM,1,1,,2,13u?etPv2;0n:dDPwUM1U1Cb069D,0*57", 'B'),
+            (b"!AIVDM,1,1,,a,13u?etPv2;0n:dDPwUM1U1Cb069D,0*04", 'A'),
+            (b"!AIVDM,1,1,,b,13u?etPv2;0n:dDPwUM1U1Cb069D,0*07", 'B'),
+        ] {
+            let sentence: Result<AisSentence> = AisParser::new().parse(line, false).unwrap().into();
+            assert_eq!(sentence.unwrap().channel, Some(channel));
+        }
+    }
+
+    #[test]
+    fn test_radio_channel() {
+        let sentence: Result<AisSentence> =
+            AisParser::new().parse(GOOD_CHECKSUM, true).unwrap().into();
+        let channel = sentence.unwrap().radio_channel();
+        assert_eq!(channel, Channel::A);
+        assert_eq!(channel.frequency(), Some(161_975_000));
+        let result = parse_nmea_sentence(NO_CHANNEL).unwrap();
+        let sentence = (result.1).1;
+        assert_eq!(sentence.radio_channel(), Channel::Unknown);
+        assert_eq!(sentence.radio_channel().frequency(), None);
+        assert_eq!(Channel::from(Some('B')).frequency(), Some(162_025_000));
+    }
+
     #[test]
     fn parse_valid_sentence_with_tag_block() {
         let result = parse_nmea_sentence(WITH_TAG_BLOCK).unwrap();
         assert_eq!(result.0, b"");
+        assert_eq!((result.1).2, 122);
+        assert_eq!((result.1).3, Some(&b"s:2573345,c:1696241893*00"[..]));
         assert_eq!(
-            (result.1).1,
-            AisSentence {
+            AisParser::new().parse(WITH_TAG_BLOCK, false).unwrap(),
+            AisFragments::Complete(AisSentence {
                 talker_id: TalkerId::AI,
                 report_type: AisReportType::VDM,
                 num_fragments: 1,
@@ -446,9 +2239,55 @@ mod tests {
                 fill_bit_count: 0,
                 message_type: 17,
                 message: None,
-            }
+                tag_block: Some(TagBlock {
+                    receiver_timestamp: Some(1696241893),
+                    source: Some("2573345".into()),
+                    ..Default::default()
+                }),
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                source_lines: SourceLines::new(),
+                checksum_mismatch: None,
+            })
         );
-        assert_eq!((result.1).2, 122);
+    }
+
+    #[test]
+    fn parse_sentence_with_corrupt_tag_block() {
+        let line = b"\\s:2573345,c:1696241893*01\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        assert_eq!(
+            AisParser::new().parse(line, false),
+            Err(Error::TagBlock(TagBlockError::ChecksumMismatch {
+                calculated: 0,
+                expected: 1,
+            }))
+        );
+        let line = b"\\s:2573345,c:16962x1893*4C\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        assert_eq!(
+            AisParser::new().parse(line, false),
+            Err(Error::TagBlock(TagBlockError::InvalidNumber))
+        );
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn position_to_line_protocol() {
+        let line = b"\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let sentence: Result<AisSentence> = AisParser::new().parse(line, true).unwrap().into();
+        assert_eq!(
+            sentence.unwrap().to_line_protocol("ais position").unwrap(),
+            "ais\\ position,mmsi=265547250 lat=57.660355,lon=11.832976,sog=13.9,cog=40.4 1696241893000000000"
+        );
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn non_position_to_line_protocol() {
+        let sentence: Result<AisSentence> =
+            AisParser::new().parse(NO_CHANNEL, false).unwrap().into();
+        assert_eq!(sentence.unwrap().to_line_protocol("ais"), None);
+        let sentence: Result<AisSentence> =
+            AisParser::new().parse(GOOD_CHECKSUM, true).unwrap().into();
+        assert_eq!(sentence.unwrap().to_line_protocol("ais"), None);
     }
 
     #[test]
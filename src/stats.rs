@@ -0,0 +1,220 @@
+//! Per-station statistics over a decoded AIS feed
+use crate::errors::Result;
+use crate::lib::std::collections::HashMap;
+use crate::lib::std::time::{Duration, Instant};
+use crate::messages::{self, AisMessage};
+use crate::sentence::AisSentence;
+
+/// Number of buckets in a [`TimingHistogram`](struct.TimingHistogram.html)
+pub const TIMING_BUCKETS: usize = 16;
+
+/// What has been seen from a single MMSI
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MmsiStats {
+    /// Number of messages received, by message type
+    pub message_counts: HashMap<u8, usize>,
+    /// Earliest receive time seen, as UNIX time in seconds
+    pub first_seen: Option<u64>,
+    /// Latest receive time seen, as UNIX time in seconds
+    pub last_seen: Option<u64>,
+}
+
+impl MmsiStats {
+    /// Total number of messages received, of any type
+    pub fn total(&self) -> usize {
+        self.message_counts.values().sum()
+    }
+
+    /// Seconds between the first and last message with a known receive time
+    pub fn time_span(&self) -> Option<u64> {
+        Some(self.last_seen? - self.first_seen?)
+    }
+}
+
+/// Collects per-MMSI message counts from decoded sentences, for spotting chatty
+/// or otherwise anomalous transmitters.
+///
+/// Receive times are taken from the `c` field of each sentence's tag block, so
+/// the time span is only known for feeds which include them.
+#[derive(Debug, Default)]
+pub struct DecodeStats {
+    stations: HashMap<u32, MmsiStats>,
+    timings: HashMap<u8, TimingHistogram>,
+}
+
+/// How long messages of one type took to decode
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TimingHistogram {
+    /// Bucket 0 counts decodes which took under 1µs, and bucket `n` those which
+    /// took from 2<sup>n-1</sup> up to 2<sup>n</sup>µs. The last bucket also
+    /// counts anything slower.
+    pub buckets: [usize; TIMING_BUCKETS],
+    pub samples: usize,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl TimingHistogram {
+    fn record(&mut self, elapsed: Duration) {
+        let micros = elapsed.as_micros();
+        let bucket = (u128::BITS - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(TIMING_BUCKETS - 1)] += 1;
+        self.samples += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        match self.samples {
+            0 => None,
+            samples => Some(Duration::from_nanos(
+                (self.total.as_nanos() / samples as u128) as u64,
+            )),
+        }
+    }
+}
+
+impl DecodeStats {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records a sentence. Sentences whose message wasn't decoded are ignored.
+    pub fn add(&mut self, sentence: &AisSentence) {
+        let message = match &sentence.message {
+            Some(message) => message,
+            None => return,
+        };
+        let stats = self.stations.entry(message.mmsi()).or_default();
+        *stats
+            .message_counts
+            .entry(message.message_type())
+            .or_default() += 1;
+        let received = sentence
+            .tag_block
+            .as_ref()
+            .and_then(|tag_block| tag_block.receiver_timestamp);
+        if let Some(received) = received {
+            stats.first_seen = Some(
+                stats
+                    .first_seen
+                    .map_or(received, |first| first.min(received)),
+            );
+            stats.last_seen = Some(stats.last_seen.map_or(received, |last| last.max(received)));
+        }
+    }
+
+    /// Decodes an unarmored message with [`messages::parse()`](../messages/fn.parse.html),
+    /// timing how long it takes. Times are recorded by message type, including for
+    /// messages which fail to decode, and are available from
+    /// [`timings()`](#method.timings).
+    ///
+    /// The decoded message isn't counted towards the per-MMSI statistics.
+    pub fn timed_parse(&mut self, unarmored: &[u8]) -> Result<AisMessage> {
+        let (_, message_type) = messages::message_type(unarmored)?;
+        let start = Instant::now();
+        let message = messages::parse(unarmored);
+        let elapsed = start.elapsed();
+        self.timings
+            .entry(message_type)
+            .or_default()
+            .record(elapsed);
+        message
+    }
+
+    /// Decode times recorded by [`timed_parse()`](#method.timed_parse), keyed by
+    /// message type
+    pub fn timings(&self) -> &HashMap<u8, TimingHistogram> {
+        &self.timings
+    }
+
+    /// The statistics collected so far, keyed by MMSI
+    pub fn stations(&self) -> &HashMap<u32, MmsiStats> {
+        &self.stations
+    }
+
+    pub fn into_stations(self) -> HashMap<u32, MmsiStats> {
+        self.stations
+    }
+}
+
+impl<'a> Extend<&'a AisSentence> for DecodeStats {
+    fn extend<I: IntoIterator<Item = &'a AisSentence>>(&mut self, sentences: I) {
+        for sentence in sentences {
+            self.add(sentence);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::AisReader;
+    use crate::sentence::{AisFragments, AisParser};
+
+    const LOG: &[u8] = b"\\c:1696241893*54\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24
+\\c:1696241953*59\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24
+!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21
+!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78
+!AIVDM,2,2,1,B,0000000,2*26
+!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21";
+
+    #[test]
+    fn decode_timings() {
+        let mut stats = DecodeStats::new();
+        let mut parser = AisParser::new();
+        for line in crate::tests::TEST_MESSAGES {
+            if let AisFragments::Complete(sentence) = parser.parse(line, false).unwrap() {
+                let unarmored =
+                    messages::unarmor(&sentence.data, sentence.fill_bit_count as usize).unwrap();
+                stats.timed_parse(&unarmored).unwrap();
+            }
+        }
+        let timings = stats.timings();
+        let mut types: Vec<_> = timings.keys().copied().collect();
+        types.sort_unstable();
+        assert_eq!(types, [4, 5, 21]);
+        assert_eq!(timings[&21].samples, 4);
+        assert_eq!(timings[&4].samples, 2);
+        assert_eq!(timings[&5].buckets.iter().sum::<usize>(), 1);
+        assert!(timings[&5].mean().unwrap() <= timings[&5].max);
+        assert_eq!(TimingHistogram::default().mean(), None);
+        // More samples than fit in a u32
+        let histogram = TimingHistogram {
+            samples: 1 << 32,
+            total: Duration::from_secs(1 << 33),
+            ..Default::default()
+        };
+        assert_eq!(histogram.mean(), Some(Duration::from_secs(2)));
+
+        let mut histogram = TimingHistogram::default();
+        for micros in [0, 3, 3, 1 << 20] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.buckets[..3], [1, 0, 2]);
+        assert_eq!(histogram.buckets[TIMING_BUCKETS - 1], 1);
+        assert_eq!(histogram.max, Duration::from_micros(1 << 20));
+        assert!(stats.stations().is_empty());
+    }
+
+    #[test]
+    fn counts_per_mmsi() {
+        let mut stats = DecodeStats::new();
+        for (_, sentence) in AisReader::new(LOG) {
+            stats.add(&sentence.unwrap());
+        }
+        let stations = stats.into_stations();
+        assert_eq!(stations.len(), 3);
+
+        let position = &stations[&265547250];
+        assert_eq!(position.message_counts.get(&1), Some(&2));
+        assert_eq!(position.total(), 2);
+        assert_eq!(position.first_seen, Some(1696241893));
+        assert_eq!(position.time_span(), Some(60));
+
+        let base_station = &stations[&3669145];
+        assert_eq!(base_station.message_counts.get(&4), Some(&2));
+        assert_eq!(base_station.message_counts.get(&1), None);
+        assert_eq!(base_station.time_span(), None);
+    }
+}
@@ -0,0 +1,315 @@
+//! NMEA 4.0 tag blocks, which carry metadata such as the receive time and source
+//! station in front of a sentence
+use crate::lib;
+use crate::lib::std::fmt;
+use crate::sentence::nmea_checksum;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type TagBlockString = lib::std::string::String;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type TagBlockString = lib::std::string::String<32>;
+
+/// The fields of a tag block, such as the `s:2573345,c:1696241893*00` in
+/// `\s:2573345,c:1696241893*00\!AIVDM,...`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TagBlock {
+    /// UNIX time the sentence was received, in seconds (`c`)
+    pub receiver_timestamp: Option<u64>,
+    /// Destination station (`d`), with any `^HH` escapes decoded
+    pub destination: Option<TagBlockString>,
+    /// Line count (`n`)
+    pub line_count: Option<u32>,
+    /// Relative time (`r`)
+    pub relative_time: Option<u64>,
+    /// Source station, often the receiver ID (`s`), with any `^HH` escapes decoded
+    pub source: Option<TagBlockString>,
+    /// Free text (`t`), with any `^HH` escapes decoded
+    pub text: Option<TagBlockString>,
+    /// Which sentence this is in a group sent together (`g`). `None` if missing
+    /// or malformed.
+    pub group: Option<TagBlockGroup>,
+}
+
+/// The `g` field of a tag block, such as `g:1-2-1234` for the first of two
+/// sentences in group 1234
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct TagBlockGroup {
+    /// 1-based position of this sentence in the group
+    pub sentence_number: u32,
+    pub total_sentences: u32,
+    pub group_id: u32,
+}
+
+impl TagBlockGroup {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut parts = data.split(|&byte| byte == b'-');
+        let mut next_number = || u32::try_from(parse_number(parts.next()?, 10)?).ok();
+        let group = Self {
+            sentence_number: next_number()?,
+            total_sentences: next_number()?,
+            group_id: next_number()?,
+        };
+        if parts.next().is_some()
+            || group.sentence_number == 0
+            || group.sentence_number > group.total_sentences
+        {
+            return None;
+        }
+        Some(group)
+    }
+}
+
+/// Why a tag block couldn't be parsed
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TagBlockError {
+    /// There is no tag block, and the parser was set to
+    /// [`require_tag_block()`](../sentence/struct.AisParserBuilder.html#method.require_tag_block)
+    Missing,
+    /// There is no `*` before the checksum
+    MissingChecksum,
+    /// The checksum isn't a hexadecimal number
+    InvalidChecksumFormat,
+    ChecksumMismatch {
+        calculated: u8,
+        expected: u8,
+    },
+    /// A field isn't of the form `key:value`
+    InvalidField,
+    /// A numeric field, such as the receive time, isn't a number
+    InvalidNumber,
+    LineCountTooLarge,
+    /// A text field isn't valid UTF-8, or has a malformed `^HH` escape
+    InvalidText,
+    /// A text field is too long to store without an allocator
+    TextTooLong,
+}
+
+impl fmt::Display for TagBlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => f.write_str("Sentence has no tag block"),
+            Self::MissingChecksum => f.write_str("Tag block has no checksum"),
+            Self::InvalidChecksumFormat => f.write_str("Invalid tag block checksum"),
+            Self::ChecksumMismatch {
+                calculated,
+                expected,
+            } => write!(
+                f,
+                "Tag block checksum mismatch; expected 0x{expected:x}, calculated 0x{calculated:x}"
+            ),
+            Self::InvalidField => f.write_str("Invalid tag block field"),
+            Self::InvalidNumber => f.write_str("Invalid number in tag block"),
+            Self::LineCountTooLarge => f.write_str("Tag block line count too large"),
+            Self::InvalidText => f.write_str("Invalid text in tag block"),
+            Self::TextTooLong => f.write_str("Tag block text too long"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TagBlockError {}
+
+type Result<T> = lib::std::result::Result<T, TagBlockError>;
+
+impl TagBlock {
+    /// Parses the contents of a tag block, without the surrounding backslashes.
+    ///
+    /// Returns an error if the checksum is missing or doesn't match, or if a known
+    /// field can't be read, other than a malformed group. Unknown fields are
+    /// ignored.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::parse_checked(data, true)
+    }
+
+    /// Like [`parse()`](#method.parse), but if `validate_checksum` is `false`, a
+    /// checksum which doesn't match is ignored. It still has to be there.
+    pub(crate) fn parse_checked(data: &[u8], validate_checksum: bool) -> Result<Self> {
+        let star = match data.iter().rposition(|&byte| byte == b'*') {
+            Some(star) => star,
+            None => return Err(TagBlockError::MissingChecksum),
+        };
+        let (body, expected) = (&data[..star], &data[star + 1..]);
+        let expected = match parse_number(expected, 16).map(u8::try_from) {
+            Some(Ok(expected)) => expected,
+            _ => return Err(TagBlockError::InvalidChecksumFormat),
+        };
+        let calculated = nmea_checksum(body);
+        if validate_checksum && calculated != expected {
+            return Err(TagBlockError::ChecksumMismatch {
+                calculated,
+                expected,
+            });
+        }
+        let mut tag_block = Self::default();
+        for field in body.split(|&byte| byte == b',') {
+            let (key, value) = match field {
+                [key, b':', value @ ..] => (*key, value),
+                _ => return Err(TagBlockError::InvalidField),
+            };
+            match key {
+                b'c' => tag_block.receiver_timestamp = Some(required_number(value)?),
+                b'd' => tag_block.destination = Some(parse_string(value)?),
+                b'n' => {
+                    let line_count = required_number(value)?;
+                    match u32::try_from(line_count) {
+                        Ok(line_count) => tag_block.line_count = Some(line_count),
+                        Err(_) => return Err(TagBlockError::LineCountTooLarge),
+                    }
+                }
+                b'r' => tag_block.relative_time = Some(required_number(value)?),
+                b's' => tag_block.source = Some(parse_string(value)?),
+                b't' => tag_block.text = Some(parse_string(value)?),
+                b'g' => tag_block.group = TagBlockGroup::parse(value),
+                _ => {}
+            }
+        }
+        Ok(tag_block)
+    }
+}
+
+/// Parses an unsigned number in the given radix
+fn parse_number(data: &[u8], radix: u32) -> Option<u64> {
+    let text = lib::std::str::from_utf8(data).ok()?;
+    // Unlike `str::parse()`, this shouldn't accept a leading `+`
+    if text.starts_with('+') {
+        return None;
+    }
+    u64::from_str_radix(text, radix).ok()
+}
+
+fn required_number(data: &[u8]) -> Result<u64> {
+    match parse_number(data, 10) {
+        Some(number) => Ok(number),
+        None => Err(TagBlockError::InvalidNumber),
+    }
+}
+
+/// Parses a text field, decoding `^HH` escapes, which stand for the byte with the
+/// hex value `HH`, such as `^2C` for a comma
+fn parse_string(data: &[u8]) -> Result<TagBlockString> {
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    let mut bytes = lib::std::vec::Vec::with_capacity(data.len());
+    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+    let mut bytes = lib::std::vec::Vec::<u8, 32>::new();
+    let mut rest = data;
+    while let [byte, tail @ ..] = rest {
+        let (byte, tail) = match (*byte, tail) {
+            (b'^', [high, low, tail @ ..]) => match parse_number(&[*high, *low], 16) {
+                Some(byte) => (byte as u8, tail),
+                None => return Err(TagBlockError::InvalidText),
+            },
+            (b'^', _) => return Err(TagBlockError::InvalidText),
+            (byte, tail) => (byte, tail),
+        };
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        bytes.push(byte);
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        bytes.push(byte).map_err(|_| TagBlockError::TextTooLong)?;
+        rest = tail;
+    }
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    return TagBlockString::from_utf8(bytes).map_err(|_| TagBlockError::InvalidText);
+    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+    {
+        let text = match lib::std::str::from_utf8(&bytes) {
+            Ok(text) => text,
+            Err(_) => return Err(TagBlockError::InvalidText),
+        };
+        let mut string = TagBlockString::new();
+        string
+            .push_str(text)
+            .map_err(|_| TagBlockError::TextTooLong)?;
+        Ok(string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tag_block() {
+        let tag_block = TagBlock::parse(b"s:2573345,c:1696241893*00").unwrap();
+        assert_eq!(tag_block.source.unwrap(), "2573345");
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_block.line_count, None);
+        assert_eq!(tag_block.destination, None);
+    }
+
+    #[test]
+    fn parse_all_fields() {
+        let tag_block =
+            TagBlock::parse(b"c:1696241893,d:DEST,n:42,r:1500,s:rx1,t:hello,x:ignored*72").unwrap();
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_block.destination.unwrap(), "DEST");
+        assert_eq!(tag_block.line_count, Some(42));
+        assert_eq!(tag_block.relative_time, Some(1500));
+        assert_eq!(tag_block.source.unwrap(), "rx1");
+        assert_eq!(tag_block.text.unwrap(), "hello");
+    }
+
+    #[test]
+    fn parse_escaped_text() {
+        let tag_block = TagBlock::parse(b"s:Foo^2CBar,t:caf^C3^A9 ^5E^5e,d:A^5CB*26").unwrap();
+        assert_eq!(tag_block.source.unwrap(), "Foo,Bar");
+        assert_eq!(tag_block.text.unwrap(), "caf\u{e9} ^^");
+        assert_eq!(tag_block.destination.unwrap(), "A\\B");
+        for data in [&b"s:bad^2*42"[..], b"t:^zz*10", b"s:^FF*17"] {
+            assert_eq!(
+                TagBlock::parse(data),
+                Err(TagBlockError::InvalidText),
+                "{:?}",
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn parse_group() {
+        let tag_block = TagBlock::parse(b"g:1-3-42,s:rx1,c:1696241893*7F").unwrap();
+        assert_eq!(
+            tag_block.group,
+            Some(TagBlockGroup {
+                sentence_number: 1,
+                total_sentences: 3,
+                group_id: 42,
+            })
+        );
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+
+        // A malformed group leaves the rest of the tag block intact
+        let tag_block = TagBlock::parse(b"g:abc,s:rx1*63").unwrap();
+        assert_eq!(tag_block.group, None);
+        assert_eq!(tag_block.source.unwrap(), "rx1");
+        assert_eq!(TagBlock::parse(b"g:1-2*73").unwrap().group, None);
+        assert_eq!(TagBlock::parse(b"g:2-1-7*69").unwrap().group, None);
+    }
+
+    #[test]
+    fn parse_invalid_tag_blocks() {
+        for (data, error) in [
+            (
+                &b"s:2573345,c:1696241893"[..],
+                TagBlockError::MissingChecksum,
+            ),
+            (
+                b"s:2573345,c:1696241893*01",
+                TagBlockError::ChecksumMismatch {
+                    calculated: 0,
+                    expected: 1,
+                },
+            ),
+            (
+                b"s:2573345,c:1696241893*zz",
+                TagBlockError::InvalidChecksumFormat,
+            ),
+            (b"s:2573345*100", TagBlockError::InvalidChecksumFormat),
+            (b"c:16962x1893*18", TagBlockError::InvalidNumber),
+            (b"nonsense*01", TagBlockError::InvalidField),
+            (b"n:99999999999*6D", TagBlockError::LineCountTooLarge),
+            (b"*00", TagBlockError::InvalidField),
+        ] {
+            assert_eq!(TagBlock::parse(data), Err(error), "{:?}", data);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Keeping track of the latest known state of each vessel in a decoded feed
+use crate::lib::std::collections::HashMap;
+use crate::lib::std::string::String;
+use crate::messages::navigation::PositionView;
+use crate::messages::types::ShipType;
+use crate::messages::AisMessage;
+
+/// Everything known about a vessel, combining its latest position report with
+/// its latest static data. Fields which haven't been received yet are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullTarget {
+    pub mmsi: u32,
+    pub latitude: Option<f32>,
+    pub longitude: Option<f32>,
+    pub speed_over_ground: Option<f32>,
+    pub course_over_ground: Option<f32>,
+    pub true_heading: Option<u16>,
+    pub name: Option<String>,
+    pub callsign: Option<String>,
+    pub ship_type: Option<ShipType>,
+    /// Distances to the bow, stern, port and starboard, in metres
+    pub dimensions: Option<(u16, u16, u16, u16)>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedVessel {
+    position: Option<PositionView>,
+    name: Option<String>,
+    callsign: Option<String>,
+    ship_type: Option<ShipType>,
+    dimensions: Option<(u16, u16, u16, u16)>,
+}
+
+/// Collects the latest position and static data sent by each MMSI.
+///
+/// Position reports replace the previous position. Static data is kept field
+/// by field, so the two parts of a type 24 report build up one picture, and a
+/// later message without a field doesn't clear it.
+///
+/// # Example:
+/// ```
+/// use ais::messages::{parse, unarmor};
+/// use ais::tracker::VesselTracker;
+///
+/// let mut tracker = VesselTracker::new();
+/// tracker.update(&parse(&unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0)?)?);
+/// let target = tracker.full_target(265547250).unwrap();
+/// assert_eq!(target.speed_over_ground, Some(13.9));
+/// assert_eq!(target.name, None);
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct VesselTracker {
+    vessels: HashMap<u32, TrackedVessel>,
+}
+
+impl VesselTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records whatever position or static data `message` carries. Messages with
+    /// neither are ignored.
+    pub fn update(&mut self, message: &AisMessage) {
+        let position = message.as_position();
+        let name = message.vessel_name();
+        let callsign = message.callsign();
+        let ship_type = message.ship_type();
+        let dimensions = message.dimensions();
+        if position.is_none()
+            && name.is_none()
+            && callsign.is_none()
+            && ship_type.is_none()
+            && dimensions.is_none()
+        {
+            return;
+        }
+        let vessel = self.vessels.entry(message.mmsi()).or_default();
+        if position.is_some() {
+            vessel.position = position;
+        }
+        if let Some(name) = name {
+            vessel.name = Some(name.into());
+        }
+        if let Some(callsign) = callsign {
+            vessel.callsign = Some(callsign.into());
+        }
+        vessel.ship_type = ship_type.or(vessel.ship_type);
+        vessel.dimensions = dimensions.or(vessel.dimensions);
+    }
+
+    /// The latest known state of the vessel with this MMSI, if anything has been
+    /// received from it
+    pub fn full_target(&self, mmsi: u32) -> Option<FullTarget> {
+        let vessel = self.vessels.get(&mmsi)?;
+        let position = vessel.position.as_ref();
+        Some(FullTarget {
+            mmsi,
+            latitude: position.and_then(|position| position.latitude),
+            longitude: position.and_then(|position| position.longitude),
+            speed_over_ground: position.and_then(|position| position.speed_over_ground),
+            course_over_ground: position.and_then(|position| position.course_over_ground),
+            true_heading: position.and_then(|position| position.true_heading),
+            name: vessel.name.clone(),
+            callsign: vessel.callsign.clone(),
+            ship_type: vessel.ship_type,
+            dimensions: vessel.dimensions,
+        })
+    }
+
+    /// Number of vessels seen
+    pub fn len(&self) -> usize {
+        self.vessels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vessels.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::position_report::PositionReport;
+    use crate::messages::AisMessageType;
+    use crate::reader::SentenceIter;
+
+    #[test]
+    fn merge_static_and_position() {
+        let mut tracker = VesselTracker::new();
+        let log =
+            b"!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78
+!AIVDM,2,2,1,B,0000000,2*26";
+        for message in SentenceIter::new(&log[..]) {
+            tracker.update(&message.unwrap());
+        }
+        let mmsi = match tracker.vessels.keys().next() {
+            Some(mmsi) => *mmsi,
+            None => panic!("Expected the type 5 message to be tracked"),
+        };
+        let target = tracker.full_target(mmsi).unwrap();
+        assert_eq!(target.name.as_deref(), Some("HAKUNAMA"));
+        assert_eq!(target.latitude, None);
+
+        // A position report from the same vessel
+        let bitstream = crate::messages::unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let mut report = PositionReport::parse(&bitstream).unwrap();
+        report.mmsi = mmsi;
+        tracker.update(&AisMessage::PositionReport(report));
+
+        let target = tracker.full_target(mmsi).unwrap();
+        assert_eq!(target.mmsi, mmsi);
+        assert_eq!(target.name.as_deref(), Some("HAKUNAMA"));
+        assert!(target.ship_type.is_some());
+        assert!(target.dimensions.is_some());
+        assert!(target.latitude.is_some());
+        assert!(target.longitude.is_some());
+        assert_eq!(target.speed_over_ground, Some(13.9));
+        assert_eq!(tracker.len(), 1);
+        assert_eq!(tracker.full_target(1), None);
+    }
+}